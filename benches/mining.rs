@@ -0,0 +1,179 @@
+//! Benchmarks of the tree construction and the full mining on synthetic transactions.
+//! Run them with `make bench` or `cargo bench --features testing`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use fp_growth::algorithm::FPGrowth;
+use fp_growth::compact::CompactTree;
+use fp_growth::source::{BitsetTransactions, TransactionSource};
+use fp_growth::testing::generate_synthetic_transactions;
+use fp_growth::tree::Tree;
+
+const ITERATIONS: u32 = 5;
+
+/// `CountingAllocator` counts the allocations and the live bytes to compare the allocator
+/// traffic and the memory usage.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Run `f` for several iterations and return the average time it takes.
+fn bench<F: FnMut()>(mut f: F) -> Duration {
+    // Warm up once before timing.
+    f();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    // (transaction count, item cardinality, average transaction length, minimum support)
+    let cases = [
+        (1_000, 100, 5, 20),
+        (10_000, 100, 5, 200),
+        (10_000, 1_000, 5, 50),
+        (10_000, 100, 15, 1_000),
+    ];
+    for &(num, items, avg_len, minimum_support) in cases.iter() {
+        let transactions = generate_synthetic_transactions(num, items, avg_len, 42);
+        let name = format!("n={} items={} avg_len={}", num, items, avg_len);
+
+        let build_time = bench(|| {
+            let mut tree = Tree::<u32>::new();
+            for transaction in transactions.iter() {
+                let mut transaction = transaction.clone();
+                transaction.sort_unstable();
+                tree.add_transaction(transaction);
+            }
+        });
+        println!("tree construction  {:<36} {:>12?}", name, build_time);
+
+        let fp_growth = FPGrowth::<u32>::new(transactions, minimum_support);
+        let mut patterns_num = 0;
+        let mining_time = bench(|| {
+            patterns_num = fp_growth.find_frequent_patterns().frequent_patterns_num();
+        });
+        println!(
+            "full mining        {:<36} {:>12?} ({} patterns with minimum support {})",
+            name, mining_time, patterns_num, minimum_support
+        );
+    }
+
+    // Dense integer items, where the bitsets are compact and checked by words.
+    let (num, items, avg_len) = (10_000, 64, 30);
+    let transactions = generate_synthetic_transactions(num, items, avg_len, 42);
+    let bitset_transactions = BitsetTransactions::from_transactions(&transactions, items).unwrap();
+    let name = format!("n={} items={} avg_len={}", num, items, avg_len);
+    let itemset = [0, 1, 2];
+
+    let vec_time = bench(|| {
+        transactions.item_supports();
+        transactions
+            .iter()
+            .filter(|transaction| itemset.iter().all(|item| transaction.contains(item)))
+            .count();
+    });
+    println!("vec counting       {:<36} {:>12?}", name, vec_time);
+    let bitset_time = bench(|| {
+        bitset_transactions.item_supports();
+        bitset_transactions.transactions_containing(&itemset);
+    });
+    println!("bitset counting    {:<36} {:>12?}", name, bitset_time);
+
+    // Dense transactions, where the partial trees are large and built again and again.
+    let (num, items, avg_len, minimum_support) = (2_000, 30, 12, 100);
+    let transactions = generate_synthetic_transactions(num, items, avg_len, 42);
+    let name = format!("n={} items={} avg_len={}", num, items, avg_len);
+    let fp_growth = FPGrowth::<u32>::new(transactions.clone(), minimum_support);
+    let pooled_fp_growth = FPGrowth::<u32>::new(transactions, minimum_support).with_node_pool();
+    for (label, fp_growth) in [
+        ("unpooled mining", &fp_growth),
+        ("pooled mining", &pooled_fp_growth),
+    ] {
+        let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+        let mining_time = bench(|| {
+            fp_growth.find_frequent_patterns();
+        });
+        let allocations =
+            (ALLOCATIONS.load(Ordering::Relaxed) - allocations_before) / (ITERATIONS as usize + 1);
+        println!(
+            "{:<18} {:<36} {:>12?} ({} allocations)",
+            label, name, mining_time, allocations
+        );
+    }
+
+    // Sparse u64 item ids, where the per-node overhead dominates the memory usage.
+    let (num, items, avg_len, minimum_support) = (10_000, 1_000, 10, 20);
+    let transactions: Vec<Vec<u64>> = generate_synthetic_transactions(num, items, avg_len, 42)
+        .into_iter()
+        .map(|transaction| {
+            let mut transaction: Vec<u64> = transaction
+                .into_iter()
+                .map(|item| u64::from(item) << 32 | 0xdead_beef)
+                .collect();
+            transaction.sort_unstable();
+            transaction
+        })
+        .collect();
+    let name = format!("n={} items={} avg_len={} u64", num, items, avg_len);
+
+    let live_bytes_before = LIVE_BYTES.load(Ordering::Relaxed);
+    let mut tree = Tree::<u64>::new();
+    for transaction in transactions.iter() {
+        tree.add_transaction(transaction.clone());
+    }
+    let tree_bytes = LIVE_BYTES.load(Ordering::Relaxed) - live_bytes_before;
+    println!(
+        "rc tree memory     {:<36} {:>12} bytes/node ({} nodes)",
+        name,
+        tree_bytes / tree.node_count(),
+        tree.node_count()
+    );
+    drop(tree);
+    let live_bytes_before = LIVE_BYTES.load(Ordering::Relaxed);
+    let mut compact_tree = CompactTree::<u64>::new();
+    for transaction in transactions.iter() {
+        compact_tree.add_transaction(transaction.clone());
+    }
+    let compact_tree_bytes = LIVE_BYTES.load(Ordering::Relaxed) - live_bytes_before;
+    println!(
+        "compact memory     {:<36} {:>12} bytes/node ({} nodes)",
+        name,
+        compact_tree_bytes / compact_tree.node_count(),
+        compact_tree.node_count()
+    );
+    drop(compact_tree);
+
+    let fp_growth = FPGrowth::<u64>::new(transactions.clone(), minimum_support);
+    let compact_fp_growth = FPGrowth::<u64>::new(transactions, minimum_support).with_compact_tree();
+    for (label, fp_growth) in [
+        ("rc tree mining", &fp_growth),
+        ("compact mining", &compact_fp_growth),
+    ] {
+        let mining_time = bench(|| {
+            fp_growth.find_frequent_patterns();
+        });
+        println!("{:<18} {:<36} {:>12?}", label, name, mining_time);
+    }
+}
@@ -2,19 +2,49 @@
 //! It implements the algorithm based on the internal data structs [`crate::tree::Node<T>`] and [`crate::tree::Tree<T>`].
 
 use std::{
-    cmp::Ordering,
-    collections::{HashMap, HashSet},
-    usize,
+    borrow::Cow,
+    cell::RefCell,
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    io, mem,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::{mpsc::Sender, OnceLock},
 };
 
-use crate::tree::Tree;
+use crate::closure::is_subset;
+use crate::compact::CompactTree;
+use crate::error::Error;
+use crate::itemset::Itemset;
+use crate::source::{
+    contains_all, count_weighted_item_supports, AggregatedTransactions, BitsetTransactions,
+    DiskTransactions, TransactionSource,
+};
+use crate::tree::{NodePool, Tree};
 use crate::ItemType;
 
+/// `MembershipRows<T>` holds the `(pattern_id, item)` rows of the patterns in long format.
+pub type MembershipRows<T> = Vec<(usize, T)>;
+/// `SupportRows` holds the `(pattern_id, support)` rows of the patterns in long format.
+pub type SupportRows = Vec<(usize, usize)>;
+
+/// `ItemExclusion<T>` tells whether an item is excluded, see [`FPGrowth::with_item_exclusion()`].
+type ItemExclusion<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Debug)]
 pub struct FPResult<T> {
-    frequent_patterns: Vec<(Vec<T>, usize)>,
-    elimination_sets: HashSet<Vec<T>>,
+    pub(crate) frequent_patterns: Vec<(Itemset<T>, usize)>,
+    pub(crate) elimination_sets: HashSet<Vec<T>>,
+    // item_supports holds the support of every single item in the mined transactions.
+    pub(crate) item_supports: HashMap<T, usize>,
+    // transactions_num is the number of the mined transactions, 0 if it's unknown.
+    pub(crate) transactions_num: usize,
+    // is_complete tells whether the mining ran to the end rather than stopped early.
+    pub(crate) is_complete: bool,
+    // support_index is built lazily on the first support query, it should not be
+    // built before the frequent patterns are all collected.
+    support_index: OnceLock<PatternTrie<T>>,
 }
 
 impl<T: ItemType> FPResult<T> {
@@ -23,17 +53,66 @@ impl<T: ItemType> FPResult<T> {
         elimination_sets: HashSet<Vec<T>>,
     ) -> FPResult<T> {
         FPResult {
-            frequent_patterns,
+            frequent_patterns: frequent_patterns
+                .into_iter()
+                .map(|(pattern, support)| (Itemset::from(pattern), support))
+                .collect(),
             elimination_sets,
+            item_supports: HashMap::new(),
+            transactions_num: 0,
+            is_complete: true,
+            support_index: OnceLock::new(),
         }
     }
 
+    /// Create a result with the given `frequent_patterns` and no elimination set,
+    /// which is handy to build a result by hand, e.g. in tests.
+    pub fn from_patterns(frequent_patterns: Vec<(Vec<T>, usize)>) -> FPResult<T> {
+        FPResult::new(frequent_patterns, HashSet::new())
+    }
+
     pub fn frequent_patterns_num(&self) -> usize {
         self.frequent_patterns.len()
     }
 
+    /// Return the mean number of items over all frequent patterns, including the single-item
+    /// ones, it is 0.0 if there is no frequent pattern.
+    pub fn average_pattern_length(&self) -> f64 {
+        if self.frequent_patterns.is_empty() {
+            return 0.0;
+        }
+        let total_length: usize = self
+            .frequent_patterns
+            .iter()
+            .map(|(pattern, _)| pattern.len())
+            .sum();
+        total_length as f64 / self.frequent_patterns.len() as f64
+    }
+
+    /// Return the frequent patterns with their items sorted.
     pub fn frequent_patterns(&self) -> Vec<(Vec<T>, usize)> {
-        self.frequent_patterns.clone()
+        self.frequent_patterns
+            .iter()
+            .map(|(pattern, support)| (pattern.to_vec(), *support))
+            .collect()
+    }
+
+    /// Return the frequent patterns as itemsets, which could be compared as sets directly.
+    pub fn itemsets(&self) -> &[(Itemset<T>, usize)] {
+        &self.frequent_patterns
+    }
+
+    /// Check whether all frequent patterns have been found, it is `false` for the partial result
+    /// of a mining stopped early, e.g. by [`FPGrowth::find_frequent_patterns_budgeted()`] or
+    /// [`FPGrowth::with_max_results()`].
+    pub fn is_complete(&self) -> bool {
+        self.is_complete
+    }
+
+    /// Return the number of the transactions this result is mined from,
+    /// it's 0 if the result isn't mined from transactions.
+    pub fn transactions_num(&self) -> usize {
+        self.transactions_num
     }
 
     pub fn elimination_sets_num(&self) -> usize {
@@ -43,117 +122,2464 @@ impl<T: ItemType> FPResult<T> {
     pub fn elimination_sets(&self) -> Vec<Vec<T>> {
         self.elimination_sets.clone().into_iter().collect()
     }
+
+    /// Return a page of at most `limit` frequent patterns starting at `offset`, in the order
+    /// of `sort` which is stable across the calls, so the pages could be fetched one by one.
+    /// Only the patterns in the page are cloned, and an `offset` past the end gives an empty page.
+    pub fn page(&self, sort: SortKey, offset: usize, limit: usize) -> Vec<(Vec<T>, usize)> {
+        let mut patterns: Vec<&(Itemset<T>, usize)> = self.frequent_patterns.iter().collect();
+        patterns.sort_by(|a, b| {
+            let ordering = match sort {
+                SortKey::Support => b.1.cmp(&a.1),
+                SortKey::Length => b.0.len().cmp(&a.0.len()).then_with(|| b.1.cmp(&a.1)),
+                SortKey::Lexicographic => Ordering::Equal,
+            };
+            ordering.then_with(|| a.0.cmp(&b.0))
+        });
+        patterns
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(pattern, support)| (pattern.to_vec(), *support))
+            .collect()
+    }
+
+    /// Return the elimination sets sorted by their length and then lexicographically,
+    /// so that the output is deterministic.
+    pub fn elimination_sets_sorted(&self) -> Vec<Vec<T>> {
+        let mut elimination_sets = self.elimination_sets();
+        elimination_sets.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        elimination_sets
+    }
+
+    /// Return the frequent patterns which contain all items of `required` and at least one
+    /// item of `any_of`. An empty `any_of` puts no constraint on the patterns.
+    pub fn patterns_matching(&self, required: &[T], any_of: &[T]) -> Vec<(Vec<T>, usize)> {
+        self.frequent_patterns
+            .iter()
+            .filter(|(pattern, _)| {
+                required.iter().all(|item| pattern.contains(item))
+                    && (any_of.is_empty() || any_of.iter().any(|item| pattern.contains(item)))
+            })
+            .map(|(pattern, support)| (pattern.to_vec(), *support))
+            .collect()
+    }
+
+    /// Return the frequent patterns which are subsets of the given `transaction` with their
+    /// supports, i.e. the item combinations of the transaction which are globally frequent.
+    pub fn frequent_subsets_of(&self, transaction: &[T]) -> Vec<(Vec<T>, usize)> {
+        let transaction = Itemset::from(transaction);
+        self.frequent_patterns
+            .iter()
+            .filter(|(pattern, _)| pattern.is_subset(&transaction))
+            .map(|(pattern, support)| (pattern.to_vec(), *support))
+            .collect()
+    }
+
+    /// Return the support of the given itemset if it is a frequent pattern, the order of the
+    /// items doesn't matter. The first query builds an index which makes the later ones take
+    /// time proportional to the size of the itemset.
+    pub fn support_of(&self, itemset: &[T]) -> Option<usize> {
+        let support_index = self.support_index.get_or_init(|| {
+            let mut trie = PatternTrie::default();
+            for (pattern, support) in self.frequent_patterns.iter() {
+                trie.insert(pattern, *support);
+            }
+            trie
+        });
+        support_index.get(itemset)
+    }
+
+    /// Return the all-confidence of the given frequent pattern, which is its support divided by
+    /// the maximum support of its items. `None` is returned if it is not a frequent pattern.
+    pub fn all_confidence(&self, pattern: &[T]) -> Option<f64> {
+        let support = self.support_of(pattern)?;
+        let max_item_support = pattern
+            .iter()
+            .filter_map(|&item| self.item_support(item))
+            .max()?;
+        Some(support as f64 / max_item_support as f64)
+    }
+
+    /// Return the support of a single item, it comes from the mined transactions, or the
+    /// single-item frequent pattern if this result isn't mined from transactions.
+    pub(crate) fn item_support(&self, item: T) -> Option<usize> {
+        match self.item_supports.get(&item) {
+            Some(&support) => Some(support),
+            None => self.support_of(&[item]),
+        }
+    }
+
+    /// Return the generators, which are the frequent patterns that have no subset with the same
+    /// support, i.e. the minimal patterns of every equivalence class sharing the same closure.
+    ///
+    /// The single-item patterns are always considered as generators.
+    pub fn generators(&self) -> Vec<(Vec<T>, usize)> {
+        let supports = self.support_map();
+        self.frequent_patterns
+            .iter()
+            .filter(|(pattern, support)| {
+                // Since the support is anti-monotone, checking the subsets which have one
+                // less item is enough.
+                pattern.len() < 2
+                    || (0..pattern.len()).all(|index| {
+                        let mut subset = pattern.to_vec();
+                        subset.remove(index);
+                        supports.get(subset.as_slice()) != Some(support)
+                    })
+            })
+            .map(|(pattern, support)| (pattern.to_vec(), *support))
+            .collect()
+    }
+
+    /// Return the isolated items, which are frequent on their own but never appear in a frequent
+    /// pattern with two or more items, ordered by the support in descending order and then by
+    /// the item. Only the items with a single-item pattern in this result are considered.
+    pub fn isolated_items(&self) -> Vec<(T, usize)> {
+        let combined_items: HashSet<T> = self
+            .frequent_patterns
+            .iter()
+            .filter(|(pattern, _)| pattern.len() >= 2)
+            .flat_map(|(pattern, _)| pattern.iter().copied())
+            .collect();
+        let mut isolated_items: Vec<(T, usize)> = self
+            .frequent_patterns
+            .iter()
+            .filter(|(pattern, _)| pattern.len() == 1 && !combined_items.contains(&pattern[0]))
+            .map(|(pattern, support)| (pattern[0], *support))
+            .collect();
+        isolated_items.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        isolated_items
+    }
+
+    /// Return the histogram of the supports of the frequent patterns as the `(upper_bound, count)`
+    /// pairs of at most `bins` bins in ascending order, where `count` is the number of the
+    /// patterns whose support is not higher than `upper_bound` but higher than the previous
+    /// bound. The bins equally divide the range from the minimum to the maximum support, they
+    /// are fewer than `bins` only if the range is narrower than that, and the empty bins are
+    /// kept. Nothing is returned if there is no frequent pattern or `bins` is 0.
+    pub fn support_histogram(&self, bins: usize) -> Vec<(usize, usize)> {
+        let supports = self.frequent_patterns.iter().map(|(_, support)| *support);
+        let (min_support, max_support) = match (supports.clone().min(), supports.clone().max()) {
+            (Some(min_support), Some(max_support)) if bins > 0 => (min_support, max_support),
+            _ => return vec![],
+        };
+        let range = max_support - min_support + 1;
+        let bin_width = range.div_ceil(bins);
+        let mut histogram: Vec<(usize, usize)> = (1..=range.div_ceil(bin_width))
+            .map(|bin| (min_support + bin * bin_width - 1, 0))
+            .collect();
+        for support in supports {
+            histogram[(support - min_support) / bin_width].1 += 1;
+        }
+        histogram
+    }
+
+    /// Return the fraction of the `test` transactions which contain at least one frequent pattern
+    /// with two or more items, it is 0.0 if there is no `test` transaction.
+    pub fn coverage(&self, test: &[Vec<T>]) -> f64 {
+        if test.is_empty() {
+            return 0.0;
+        }
+        let covered = test
+            .iter()
+            .filter(|transaction| {
+                let items: HashSet<&T> = transaction.iter().collect();
+                self.frequent_patterns.iter().any(|(pattern, _)| {
+                    pattern.len() >= 2 && pattern.iter().all(|item| items.contains(item))
+                })
+            })
+            .count();
+        covered as f64 / test.len() as f64
+    }
+
+    /// Return the same fraction as [`FPResult::coverage()`] for the `test` transactions held as
+    /// bitsets, every pattern is checked against a transaction with a few word operations.
+    pub fn bitset_coverage(&self, test: &BitsetTransactions) -> f64
+    where
+        T: Into<u32>,
+    {
+        if test.transactions_num() == 0 {
+            return 0.0;
+        }
+        // The patterns with any item out of the universe could never be contained.
+        let masks: Vec<Vec<u64>> = self
+            .frequent_patterns
+            .iter()
+            .filter(|(pattern, _)| pattern.len() >= 2)
+            .filter_map(|(pattern, _)| {
+                let items: Vec<u32> = pattern.iter().map(|&item| item.into()).collect();
+                test.to_bitset(&items).ok()
+            })
+            .collect();
+        let covered = test
+            .bitsets()
+            .filter(|bitset| masks.iter().any(|mask| contains_all(bitset, mask)))
+            .count();
+        covered as f64 / test.transactions_num() as f64
+    }
+
+    /// Convert the frequent patterns into a normalized long format, where the pattern ID is the
+    /// index of the pattern. It returns the `(pattern_id, item)` membership rows and the
+    /// `(pattern_id, support)` support rows.
+    pub fn to_long_format(&self) -> (MembershipRows<T>, SupportRows) {
+        let mut membership_rows = vec![];
+        let mut support_rows = Vec::with_capacity(self.frequent_patterns.len());
+        for (pattern_id, (pattern, support)) in self.frequent_patterns.iter().enumerate() {
+            membership_rows.extend(pattern.iter().map(|&item| (pattern_id, item)));
+            support_rows.push((pattern_id, *support));
+        }
+        (membership_rows, support_rows)
+    }
+
+    /// Estimate the heap memory used by this result in bytes.
+    ///
+    /// It's an approximation which assumes every frequent pattern takes its items plus a
+    /// `(Itemset<T>, usize)` entry, every elimination set takes its items plus a `Vec<T>` entry,
+    /// the spare capacity of vectors and the overhead of the hash set are ignored.
+    pub fn estimated_bytes(&self) -> usize {
+        let item_size = mem::size_of::<T>();
+        let frequent_patterns_bytes: usize = self
+            .frequent_patterns
+            .iter()
+            .map(|(pattern, _)| pattern.len() * item_size + mem::size_of::<(Itemset<T>, usize)>())
+            .sum();
+        let elimination_sets_bytes: usize = self
+            .elimination_sets
+            .iter()
+            .map(|set| set.len() * item_size + mem::size_of::<Vec<T>>())
+            .sum();
+        frequent_patterns_bytes + elimination_sets_bytes
+    }
+
+    /// Build a mapping from every frequent pattern to its support.
+    fn support_map(&self) -> HashMap<Itemset<T>, usize> {
+        self.frequent_patterns.iter().cloned().collect()
+    }
+}
+
+/// `PatternTrie<T>` is a prefix tree of the frequent patterns whose items are sorted.
+#[derive(Clone, Debug)]
+struct PatternTrie<T> {
+    support: Option<usize>,
+    children: HashMap<T, PatternTrie<T>>,
+}
+
+impl<T> Default for PatternTrie<T> {
+    fn default() -> Self {
+        PatternTrie {
+            support: None,
+            children: HashMap::new(),
+        }
+    }
+}
+
+impl<T: ItemType> PatternTrie<T> {
+    fn insert(&mut self, pattern: &Itemset<T>, support: usize) {
+        let mut cur_trie = self;
+        for &item in pattern.iter() {
+            cur_trie = cur_trie.children.entry(item).or_default();
+        }
+        cur_trie.support = Some(support);
+    }
+
+    fn get(&self, itemset: &[T]) -> Option<usize> {
+        let mut cur_trie = self;
+        for item in Itemset::from(itemset).iter() {
+            cur_trie = cur_trie.children.get(item)?;
+        }
+        cur_trie.support
+    }
+}
+
+/// `PatternExplanation<T>` explains where the support of a frequent pattern comes from, it
+/// holds the root-to-node paths in the FP-Growth tree which contribute to the support.
+#[derive(Clone, Debug)]
+pub struct PatternExplanation<T> {
+    pattern: Vec<T>,
+    support: usize,
+    paths: Vec<Vec<(T, usize)>>,
+}
+
+impl<T: ItemType> PatternExplanation<T> {
+    pub fn pattern(&self) -> &[T] {
+        &self.pattern
+    }
+
+    pub fn support(&self) -> usize {
+        self.support
+    }
+
+    /// Return the contributing paths, every path is a list of `(item, count)` from the child of
+    /// the root node to the node whose count is added to the support of the pattern.
+    pub fn paths(&self) -> &[Vec<(T, usize)>] {
+        &self.paths
+    }
+}
+
+/// `SortKey` represents the orders of the frequent patterns in a page of [`FPResult::page()`],
+/// the ties are always broken lexicographically by the sorted items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    /// The support in descending order.
+    Support,
+    /// The number of items in descending order, and then the support in descending order.
+    Length,
+    /// The sorted items lexicographically.
+    Lexicographic,
+}
+
+/// `Support` represents the minimum support of [`FPGrowth`], either as the number of
+/// transactions or as the fraction of all transactions. A `usize` converts into the former
+/// and a `f64` into the latter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Support {
+    /// The minimum number of transactions holding a frequent pattern.
+    Absolute(usize),
+    /// The minimum fraction of the transactions holding a frequent pattern, it's rounded up to
+    /// the number of transactions, e.g. `Relative(0.005)` is 5 out of 1000 transactions.
+    Relative(f64),
+}
+
+impl Support {
+    /// Return the minimum number of transactions among the `transactions_num` transactions.
+    /// A negative or NaN fraction counts as 0.
+    pub fn absolute(self, transactions_num: usize) -> usize {
+        match self {
+            Support::Absolute(minimum_support) => minimum_support,
+            Support::Relative(fraction) => (fraction * transactions_num as f64).ceil() as usize,
+        }
+    }
+}
+
+impl From<usize> for Support {
+    fn from(minimum_support: usize) -> Self {
+        Support::Absolute(minimum_support)
+    }
+}
+
+impl From<f64> for Support {
+    fn from(fraction: f64) -> Self {
+        Support::Relative(fraction)
+    }
+}
+
+/// `PatternStats` holds the numbers of all, closed and maximal frequent patterns of a mining.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PatternStats {
+    all_count: usize,
+    closed_count: usize,
+    maximal_count: usize,
+}
+
+impl PatternStats {
+    pub fn all_count(&self) -> usize {
+        self.all_count
+    }
+
+    pub fn closed_count(&self) -> usize {
+        self.closed_count
+    }
+
+    pub fn maximal_count(&self) -> usize {
+        self.maximal_count
+    }
+}
+
+/// `InputReport<T>` holds the data-quality statistics of the transactions to be mined, the
+/// items of every transaction are counted once no matter how many times they are repeated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputReport<T> {
+    transactions_num: usize,
+    empty_count: usize,
+    duplicate_count: usize,
+    min_len: usize,
+    max_len: usize,
+    avg_len: f64,
+    singleton_items: Vec<T>,
+}
+
+impl<T: ItemType> InputReport<T> {
+    pub fn transactions_num(&self) -> usize {
+        self.transactions_num
+    }
+
+    /// Return the number of the transactions without any item.
+    pub fn empty_count(&self) -> usize {
+        self.empty_count
+    }
+
+    /// Return the number of the transactions holding the same items as an earlier one, i.e. the
+    /// first occurrence of a transaction is not counted.
+    pub fn duplicate_count(&self) -> usize {
+        self.duplicate_count
+    }
+
+    /// Return the minimum number of the distinct items in a transaction, 0 if there is none.
+    pub fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    /// Return the maximum number of the distinct items in a transaction, 0 if there is none.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Return the average number of the distinct items in a transaction, 0.0 if there is none.
+    pub fn avg_len(&self) -> f64 {
+        self.avg_len
+    }
+
+    /// Return the sorted items which appear in exactly one transaction.
+    pub fn singleton_items(&self) -> &[T] {
+        &self.singleton_items
+    }
+}
+
+/// `SupportPredicate` decides whether a pattern is frequent by its `support` and the number of
+/// its items `pattern_len`, e.g. with a length-adjusted or statistically derived threshold.
+/// It's implemented for any `Fn(usize, usize) -> bool` closure which is `Send` and `Sync`, so
+/// the [`FPGrowth`] holding it could still be shared across threads.
+pub trait SupportPredicate: Send + Sync {
+    fn is_frequent(&self, support: usize, pattern_len: usize) -> bool;
+}
+
+impl<F: Fn(usize, usize) -> bool + Send + Sync> SupportPredicate for F {
+    fn is_frequent(&self, support: usize, pattern_len: usize) -> bool {
+        self(support, pattern_len)
+    }
 }
 
 /// `FPGrowth<T>` represents an algorithm instance, it should include the `transactions` input
 /// and minimum support value as the initial config. Once it is created, you could run
 /// [`FPGrowth::find_frequent_patterns()`] to start the frequent pattern mining.
+///
+/// The transactions are held in memory by default, any other [`TransactionSource`] could be used
+/// with [`FPGrowth::from_source()`], e.g. the [`DiskTransactions`] by [`FPGrowth::from_disk()`].
 // `transactions` will be sorted and deduplicated before starting the algorithm.
 #[allow(clippy::upper_case_acronyms)]
-pub struct FPGrowth<T> {
-    transactions: Vec<Vec<T>>,
+pub struct FPGrowth<T, S = Vec<Vec<T>>> {
+    transactions: S,
     minimum_support: usize,
+    // max_nodes is the maximum number of nodes the FP-Growth tree could hold.
+    max_nodes: Option<usize>,
+    // item_priority is used to sort the items in every transaction before the support.
+    item_priority: HashMap<T, i64>,
+    // max_depth is the maximum length of the frequent patterns to be mined.
+    max_depth: Option<usize>,
+    // max_results is the maximum number of frequent patterns to be emitted.
+    max_results: Option<usize>,
+    // other_item replaces the items whose support is lower than the minimum support.
+    other_item: Option<T>,
+    // mutex_groups are the groups of items which must not occur in the same pattern.
+    mutex_groups: Vec<HashSet<T>>,
+    // include_singletons tells whether the single-item patterns are in the output.
+    include_singletons: bool,
+    // min_length is the minimum length of the frequent patterns in the output.
+    min_length: usize,
+    // anchor_items must all be contained by every frequent pattern in the output.
+    anchor_items: HashSet<T>,
+    // item_normalizer maps every item before it's counted.
+    item_normalizer: Option<Box<dyn Fn(T) -> T + Send + Sync>>,
+    // excluded_items are stripped after being normalized, before they're counted.
+    excluded_items: HashSet<T>,
+    // item_exclusion tells whether a normalized item is stripped before it's counted.
+    item_exclusion: Option<ItemExclusion<T>>,
+    // item_ancestors maps every item to all its ancestors in the item hierarchy.
+    item_ancestors: HashMap<T, Vec<T>>,
+    // support_predicate replaces the minimum support check of the patterns if it's set.
+    support_predicate: Option<Box<dyn SupportPredicate>>,
+    // use_node_pool tells whether the nodes of the partial trees are reused during a mining.
+    use_node_pool: bool,
+    // compact_tree tells whether the trees are built as the compact trees.
+    compact_tree: bool,
+    // inverted_index maps every item to the bitset of the transactions containing it, it's
+    // built by the first query of the transactions containing an itemset.
+    inverted_index: OnceLock<HashMap<T, Vec<u64>>>,
 }
 
 impl<T: ItemType> FPGrowth<T> {
-    /// Create a FP-Growth algorithm instance with the given `transactions` and `minimum_support`.
-    pub fn new(transactions: Vec<Vec<T>>, minimum_support: usize) -> FPGrowth<T> {
-        FPGrowth {
-            transactions,
+    /// Create a FP-Growth algorithm instance with the given `transactions` and `minimum_support`,
+    /// which is either a number of transactions or a fraction of them, see [`Support`].
+    pub fn new(transactions: Vec<Vec<T>>, minimum_support: impl Into<Support>) -> FPGrowth<T> {
+        FPGrowth::from_source(transactions, minimum_support)
+    }
+
+    /// Create a FP-Growth algorithm instance like [`FPGrowth::new()`], but validate the config first.
+    /// [`Error::SupportExceedsTransactions`] is returned if `minimum_support` is larger than the
+    /// number of transactions, in which case no pattern could ever be frequent.
+    pub fn try_new(
+        transactions: Vec<Vec<T>>,
+        minimum_support: impl Into<Support>,
+    ) -> Result<FPGrowth<T>, Error> {
+        let fp_growth = FPGrowth::new(transactions, minimum_support);
+        match fp_growth.is_support_satisfiable() {
+            true => Ok(fp_growth),
+            false => Err(Error::SupportExceedsTransactions {
+                minimum_support: fp_growth.minimum_support,
+                transactions_num: fp_growth.transactions.len(),
+            }),
+        }
+    }
+
+    /// Mine the frequent patterns from an externally maintained `tree` whose support is not
+    /// lower than `minimum_support`, e.g. a live tree updated by a streaming application.
+    ///
+    /// The items of every transaction in the `tree` should be inserted in the same order.
+    pub fn mine_existing_tree(tree: &Tree<T>, minimum_support: usize) -> FPResult<T> {
+        let mut fp_result = FPResult::new(vec![], HashSet::new());
+        FPGrowth::new(vec![], minimum_support).find_with_suffix(
+            tree,
+            &[],
+            minimum_support,
+            &mut fp_result,
+        );
+        fp_result
+    }
+}
+
+impl FPGrowth<u32, DiskTransactions> {
+    /// Create a FP-Growth algorithm instance which reads the transactions lazily from the file
+    /// at `path` during every pass of the algorithm, see [`DiskTransactions`] for the file format.
+    /// Only the FP-Growth tree is held in memory rather than the transactions.
+    pub fn from_disk<P: AsRef<Path>>(
+        path: P,
+        minimum_support: impl Into<Support>,
+    ) -> io::Result<FPGrowth<u32, DiskTransactions>> {
+        Ok(FPGrowth::from_source(
+            DiskTransactions::open(path)?,
             minimum_support,
+        ))
+    }
+}
+
+impl<T: ItemType> FPGrowth<T, AggregatedTransactions<T>> {
+    /// Create a FP-Growth algorithm instance with the distinct transactions and the numbers of
+    /// their occurrences, see [`AggregatedTransactions`]. Every transaction is added to the tree
+    /// at once, and the result is the same as repeating it as many times as it occurs.
+    pub fn from_aggregated(
+        data: Vec<(Vec<T>, usize)>,
+        minimum_support: impl Into<Support>,
+    ) -> FPGrowth<T, AggregatedTransactions<T>> {
+        FPGrowth::from_source(AggregatedTransactions::new(data), minimum_support)
+    }
+}
+
+impl<T: ItemType, S: TransactionSource<T>> FPGrowth<T, S> {
+    /// Create a FP-Growth algorithm instance with the transactions from the given `source`.
+    pub fn from_source(source: S, minimum_support: impl Into<Support>) -> FPGrowth<T, S> {
+        FPGrowth {
+            minimum_support: resolve_minimum_support(&source, minimum_support.into()),
+            transactions: source,
+            max_nodes: None,
+            item_priority: HashMap::new(),
+            max_depth: None,
+            max_results: None,
+            other_item: None,
+            mutex_groups: vec![],
+            include_singletons: true,
+            min_length: 1,
+            anchor_items: HashSet::new(),
+            item_normalizer: None,
+            excluded_items: HashSet::new(),
+            item_exclusion: None,
+            item_ancestors: HashMap::new(),
+            support_predicate: None,
+            use_node_pool: false,
+            compact_tree: false,
+            inverted_index: OnceLock::new(),
+        }
+    }
+
+    /// Check whether the minimum support could be satisfied at all, i.e. it is not larger
+    /// than the number of transactions.
+    pub fn is_support_satisfiable(&self) -> bool {
+        self.minimum_support <= self.transactions.transactions_num()
+    }
+
+    /// Replace the minimum support, a fraction is resolved against the number of transactions.
+    pub fn with_minimum_support(mut self, minimum_support: impl Into<Support>) -> FPGrowth<T, S> {
+        self.minimum_support = resolve_minimum_support(&self.transactions, minimum_support.into());
+        self
+    }
+
+    /// Limit the FP-Growth tree to hold at most `max_nodes` nodes.
+    ///
+    /// Once the budget is exceeded during the tree construction,
+    /// [`FPGrowth::try_find_frequent_patterns()`] aborts with [`Error::MemoryBudgetExceeded`],
+    /// while [`FPGrowth::find_frequent_patterns()`] raises the minimum support and rebuilds
+    /// the tree until it fits into the budget.
+    pub fn with_memory_budget(mut self, max_nodes: usize) -> FPGrowth<T, S> {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Sort the items in every transaction by the given `item_priority` in descending order
+    /// before inserting them into the tree, the items without a priority come last and are
+    /// still sorted by their support.
+    ///
+    /// It only changes the shape of the tree and the cost to build it, the frequent patterns
+    /// found are the same.
+    pub fn with_item_priority(mut self, item_priority: HashMap<T, i64>) -> FPGrowth<T, S> {
+        self.item_priority = item_priority;
+        self
+    }
+
+    /// Stop the mining from going deeper once the frequent patterns have `max_depth` items,
+    /// so no partial tree is built for them and no longer pattern is found.
+    pub fn with_max_depth(mut self, max_depth: usize) -> FPGrowth<T, S> {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Only emit the frequent patterns with `min_length` to `max_length` items. The mining stops
+    /// going deeper at `max_length` like [`FPGrowth::with_max_depth()`], while the shorter
+    /// patterns are still mined to be extended, just not emitted.
+    ///
+    /// `max_length` is the max depth, so it overwrites the one set by
+    /// [`FPGrowth::with_max_depth()`] before, and is overwritten by it afterwards. It panics if
+    /// `min_length` is larger than `max_length`, which no pattern could satisfy.
+    pub fn with_pattern_length(mut self, min_length: usize, max_length: usize) -> FPGrowth<T, S> {
+        assert!(
+            min_length <= max_length,
+            "the minimum pattern length {} is larger than the maximum {}",
+            min_length,
+            max_length
+        );
+        self.min_length = min_length;
+        self.max_depth = Some(max_length);
+        self
+    }
+
+    /// Stop the mining once `max_results` frequent patterns have been emitted, as a safety valve
+    /// against the runaway output. Unlike the top-K mining, the patterns kept are just the first
+    /// ones found rather than the best ones, and [`FPResult::is_complete()`] tells whether any
+    /// pattern has been left out. It applies to [`FPGrowth::find_frequent_patterns()`],
+    /// [`FPGrowth::try_find_frequent_patterns()`] and [`FPGrowth::mine_to_channel()`].
+    pub fn with_max_results(mut self, max_results: usize) -> FPGrowth<T, S> {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    /// Replace the items whose support is lower than the minimum support with `other_item` in
+    /// every transaction instead of dropping them, so the patterns could tell that something rare
+    /// was bought. `other_item` is counted once per transaction and is mined like any other item,
+    /// its support is the number of transactions holding at least one infrequent item.
+    ///
+    /// `other_item` must not collide with any real item in the transactions.
+    pub fn with_other_bucket(mut self, other_item: T) -> FPGrowth<T, S> {
+        self.other_item = Some(other_item);
+        self
+    }
+
+    /// Suppress the patterns containing two or more items from the same group in `mutex_groups`,
+    /// since the items in a group are mutually exclusive.
+    ///
+    /// The constraint is checked during the recursion, a conflicting item is never appended to
+    /// a pattern, so no partial tree is built for it and its extensions. The supports of the
+    /// other patterns are unaffected.
+    pub fn with_mutex_groups(mut self, mutex_groups: Vec<HashSet<T>>) -> FPGrowth<T, S> {
+        self.mutex_groups = mutex_groups;
+        self
+    }
+
+    /// Only emit the frequent patterns containing all the `anchor_items`, e.g. a promoted product.
+    ///
+    /// The anchor items are ordered last in the tree, so a pattern containing them is always
+    /// mined from their partial trees, and the partial trees of the other items are never built
+    /// until the suffix contains all the anchor items. Note that the items are normalized before
+    /// being mined, so the anchor items should be normalized ones.
+    pub fn with_anchor_items(mut self, anchor_items: HashSet<T>) -> FPGrowth<T, S> {
+        self.anchor_items = anchor_items;
+        self
+    }
+
+    /// Mine the generalized frequent patterns with the item hierarchy given by `parents`, which
+    /// maps an item to its parent, e.g. "2% milk" to "milk" and "milk" to "dairy". Every
+    /// transaction is augmented with all ancestors of its items before counting, so the patterns
+    /// could be made up of the items at any level of the hierarchy. Note that the supports of
+    /// the ancestors count the transactions holding any of their descendants.
+    ///
+    /// The patterns containing an item together with its ancestor are suppressed, since their
+    /// support is always the same as the pattern without the ancestor.
+    /// [`Error::CyclicHierarchy`] is returned if any item is an ancestor of itself.
+    pub fn with_item_hierarchy(mut self, parents: HashMap<T, T>) -> Result<FPGrowth<T, S>, Error> {
+        let mut item_ancestors = HashMap::with_capacity(parents.len());
+        for &item in parents.keys() {
+            let mut ancestors: Vec<T> = vec![];
+            let mut cur_item = item;
+            while let Some(&parent) = parents.get(&cur_item) {
+                if parent == item || ancestors.contains(&parent) {
+                    return Err(Error::CyclicHierarchy {
+                        item: format!("{:?}", parent),
+                    });
+                }
+                ancestors.push(parent);
+                cur_item = parent;
+            }
+            item_ancestors.insert(item, ancestors);
         }
+        self.item_ancestors = item_ancestors;
+        self.inverted_index = OnceLock::new();
+        Ok(self)
+    }
+
+    /// Set whether the single-item patterns are in the output, they are included by default.
+    /// They are still mined as usual when excluded, so the longer patterns are the same.
+    pub fn include_singletons(mut self, include_singletons: bool) -> FPGrowth<T, S> {
+        self.include_singletons = include_singletons;
+        self
+    }
+
+    /// Map every item with `item_normalizer` before counting the supports and building the tree,
+    /// so the items normalized to the same one are merged, e.g. the strings in different cases.
+    ///
+    /// The normalizer should be consistent and idempotent, i.e. `f(f(x)) == f(x)`, otherwise
+    /// the results would be hard to make sense of.
+    pub fn with_item_normalizer(
+        mut self,
+        item_normalizer: impl Fn(T) -> T + Send + Sync + 'static,
+    ) -> FPGrowth<T, S> {
+        self.item_normalizer = Some(Box::new(item_normalizer));
+        self.inverted_index = OnceLock::new();
+        self
+    }
+
+    /// Strip the `excluded_items` from every transaction before counting the supports and
+    /// building the tree, see [`FPGrowth::with_item_exclusion()`].
+    pub fn with_excluded_items(mut self, excluded_items: HashSet<T>) -> FPGrowth<T, S> {
+        self.excluded_items = excluded_items;
+        self.inverted_index = OnceLock::new();
+        self
+    }
+
+    /// Strip the items for which `item_exclusion` returns true from every transaction before
+    /// counting the supports and building the tree, just like cleaning the transactions first.
+    /// It works together with [`FPGrowth::with_excluded_items()`].
+    /// The excluded items never appear in the patterns or the elimination sets, while the
+    /// transactions are still counted even if they are left empty.
+    ///
+    /// The items are checked after being normalized. An excluded item adds no support to its
+    /// ancestors in the item hierarchy, and an excluded ancestor is never augmented.
+    pub fn with_item_exclusion(
+        mut self,
+        item_exclusion: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> FPGrowth<T, S> {
+        self.item_exclusion = Some(Box::new(item_exclusion));
+        self.inverted_index = OnceLock::new();
+        self
+    }
+
+    /// Set the `support_predicate` to decide whether a pattern is frequent instead of comparing
+    /// its support with the minimum support. The items whose support is lower than the minimum
+    /// support are still pruned when building the tree, so the minimum support should be set to
+    /// the lowest support the predicate could accept. The extensions of a pattern rejected by
+    /// the predicate are never visited.
+    pub fn with_support_predicate(
+        mut self,
+        support_predicate: impl SupportPredicate + 'static,
+    ) -> FPGrowth<T, S> {
+        self.support_predicate = Some(Box::new(support_predicate));
+        self
+    }
+
+    /// Reuse the nodes of the partial trees during the mining, rather than allocating new nodes
+    /// for every partial tree and dropping them afterwards, which reduces the allocations on
+    /// dense data. Every mining has a pool of its own, which is dropped once it finishes.
+    pub fn with_node_pool(mut self) -> FPGrowth<T, S> {
+        self.use_node_pool = true;
+        self
+    }
+
+    /// Build the trees as [`CompactTree`]s rather than the `Rc<Node<T>>` graphs, which takes much
+    /// less memory per node, e.g. for the datasets with a huge number of distinct `u64` item ids.
+    /// The node pool is not used by the compact trees. It applies to
+    /// [`FPGrowth::find_frequent_patterns()`] and [`FPGrowth::try_find_frequent_patterns()`].
+    pub fn with_compact_tree(mut self) -> FPGrowth<T, S> {
+        self.compact_tree = true;
+        self
     }
 
     /// Find frequent patterns in the given transactions using FP-Growth.
     pub fn find_frequent_patterns(&self) -> FPResult<T> {
-        // Collect and preprocess the transactions.
-        let mut items = HashMap::new();
-        for transaction in self.transactions.clone().into_iter() {
-            let mut item_set: HashSet<T> = HashSet::new();
-            for &item in transaction.iter() {
-                // Check whether we have inserted the same item in a transaction before,
-                // make sure we won't calculate the wrong support.
-                match item_set.contains(&item) {
-                    true => continue,
-                    false => {
-                        item_set.insert(item);
-                        let count = items.entry(item).or_insert(0);
-                        *count += 1;
-                    }
-                };
+        self.find_frequent_patterns_filtered(&HashSet::new())
+    }
+
+    /// Find frequent patterns in the given transactions using FP-Growth like
+    /// [`FPGrowth::find_frequent_patterns()`], but skip the transactions which fail to be
+    /// preprocessed rather than aborting, i.e. the ones holding an item the item normalizer
+    /// panics on. The result is returned with the indexes of the skipped transactions, and its
+    /// number of transactions doesn't count them.
+    ///
+    /// Every distinct item is normalized once more to check it. The panics are caught, but
+    /// still reported by the panic hook as usual. Note that nothing could be caught if the
+    /// crate is built with `panic = "abort"`, then a panicking normalizer still aborts.
+    pub fn find_frequent_patterns_lenient(&self) -> (FPResult<T>, Vec<usize>) {
+        let item_normalizer = match &self.item_normalizer {
+            Some(item_normalizer) => item_normalizer,
+            None => return (self.find_frequent_patterns(), vec![]),
+        };
+        let mut item_validity: HashMap<T, bool> = HashMap::new();
+        let mut skipped_transactions = vec![];
+        for (index, transaction) in self.transactions.transactions().enumerate() {
+            let is_valid = transaction.iter().all(|&item| {
+                *item_validity.entry(item).or_insert_with(|| {
+                    panic::catch_unwind(AssertUnwindSafe(|| item_normalizer(item))).is_ok()
+                })
+            });
+            if !is_valid {
+                skipped_transactions.push(index);
             }
         }
+        if skipped_transactions.is_empty() {
+            return (self.find_frequent_patterns(), skipped_transactions);
+        }
 
-        // Clean up the items whose support is lower than the minimum_support.
-        let cleaned_items: HashMap<&T, &usize> = items
-            .iter()
-            .filter(|(_, &count)| count >= self.minimum_support)
+        let invalid_items: HashSet<T> = item_validity
+            .into_iter()
+            .filter(|&(_, is_valid)| !is_valid)
+            .map(|(item, _)| item)
             .collect();
-        let mut elimination_sets = HashSet::new();
+        let mut fp_result = self.find_frequent_patterns_filtered(&invalid_items);
+        fp_result.transactions_num -= skipped_transactions.len();
+        (fp_result, skipped_transactions)
+    }
 
-        let mut tree = Tree::<T>::new();
-        for transaction in self.transactions.clone().into_iter() {
-            let mut cleaned_transaction: Vec<T> = transaction
-                .clone()
-                .into_iter()
-                .filter(|item| cleaned_items.contains_key(item))
-                .collect();
-            if cleaned_transaction.len() != transaction.len() {
-                elimination_sets.insert(transaction);
-            }
-            cleaned_transaction.sort_by(|a, b| {
-                let &a_counter = cleaned_items.get(a).unwrap();
-                let &b_counter = cleaned_items.get(b).unwrap();
-                match b_counter.cmp(a_counter) {
-                    Ordering::Equal => {
-                        // When counter is the same, we will sort by T itself.
-                        // e.g. ["c", "b", "a"] -> ["a", "b", "c"]
-                        match b.cmp(a) {
-                            Ordering::Greater => Ordering::Less,
-                            Ordering::Less => Ordering::Greater,
-                            Ordering::Equal => Ordering::Equal,
-                        }
+    /// Find frequent patterns in the transactions holding none of the `skipped_items`.
+    fn find_frequent_patterns_filtered(&self, skipped_items: &HashSet<T>) -> FPResult<T> {
+        match self.compact_tree {
+            true => self.find_frequent_patterns_in::<CompactTree<T>>(skipped_items),
+            false => self.find_frequent_patterns_in::<Tree<T>>(skipped_items),
+        }
+    }
+
+    /// Find frequent patterns in the transactions holding none of the `skipped_items` using
+    /// FP-Growth with the tree `G`.
+    fn find_frequent_patterns_in<G: MiningTree<T>>(
+        &self,
+        skipped_items: &HashSet<T>,
+    ) -> FPResult<T> {
+        let items = self.count_items_filtered(skipped_items);
+        let (tree, elimination_sets, minimum_support) =
+            self.build_fitting_tree_filtered::<G>(&items, self.minimum_support, skipped_items);
+        let mut fp_result = FPResult::new(vec![], elimination_sets);
+        fp_result.is_complete = self.find_with_max_results(&tree, minimum_support, &mut fp_result);
+        fp_result.item_supports = items;
+        fp_result.transactions_num = self.transactions.transactions_num();
+        fp_result
+    }
+
+    /// Return an iterator mining a sliding window of the latest `window` transactions in the
+    /// `stream`, the transactions of this instance are not used. The first call of `next()`
+    /// ingests `window` transactions, and every later one slides the window by ingesting the
+    /// next `step` transactions and expiring the oldest ones. It returns the frequent patterns
+    /// of the current window until the stream runs out.
+    ///
+    /// The live tree keeps the items of every transaction sorted by themselves rather than by
+    /// their supports, so the item priority doesn't apply, and no elimination set is collected.
+    pub fn window_miner<I: IntoIterator<Item = Vec<T>>>(
+        &self,
+        stream: I,
+        window: usize,
+        step: usize,
+    ) -> WindowMiner<'_, T, S, I::IntoIter> {
+        WindowMiner {
+            fp_growth: self,
+            stream: stream.into_iter(),
+            window: VecDeque::new(),
+            window_size: window,
+            step,
+            tree: Tree::new(),
+            is_started: false,
+        }
+    }
+
+    /// Find frequent patterns in the given transactions using FP-Growth, every pattern is
+    /// returned with its support divided by the number of transactions, i.e. in [0, 1].
+    pub fn find_frequent_patterns_normalized(&self) -> Vec<(Vec<T>, f64)> {
+        let fp_result = self.find_frequent_patterns();
+        let transactions_num = fp_result.transactions_num();
+        if transactions_num == 0 {
+            return vec![];
+        }
+        fp_result
+            .frequent_patterns
+            .into_iter()
+            .map(|(pattern, support)| {
+                (pattern.into_vec(), support as f64 / transactions_num as f64)
+            })
+            .collect()
+    }
+
+    /// Find the frequent patterns of one or two items by counting them directly in a pass over
+    /// the transactions, which is much faster than building and mining the trees. The result
+    /// is the same as [`FPGrowth::find_frequent_patterns()`] with a maximum depth of 2, except
+    /// that no elimination set is collected, the other bucket set by
+    /// [`FPGrowth::with_other_bucket()`] is not counted, and the memory budget doesn't apply
+    /// since no tree is built.
+    pub fn find_pairs(&self) -> FPResult<T> {
+        let items = self.count_items();
+        let frequent_items: HashSet<T> = items
+            .iter()
+            .filter(|(_, &support)| {
+                support >= self.minimum_support
+                    && self.is_frequent(support, 1, self.minimum_support)
+            })
+            .map(|(&item, _)| item)
+            .collect();
+        let mut pair_supports: HashMap<(T, T), usize> = HashMap::new();
+        if !self.reach_max_depth(1) {
+            let mut transaction_items: Vec<T> = vec![];
+            for transaction in self.normalized_transactions() {
+                transaction_items.clear();
+                transaction_items.extend(
+                    transaction
+                        .iter()
+                        .filter(|item| frequent_items.contains(item)),
+                );
+                transaction_items.sort_unstable();
+                transaction_items.dedup();
+                for (position, &a) in transaction_items.iter().enumerate() {
+                    for &b in transaction_items[position + 1..].iter() {
+                        *pair_supports.entry((a, b)).or_default() += 1;
                     }
-                    Ordering::Less => Ordering::Less,
-                    Ordering::Greater => Ordering::Greater,
                 }
-            });
-            // After sort cleaned_transaction, remove consecutive items from it then.
-            cleaned_transaction.dedup();
-            tree.add_transaction(cleaned_transaction);
+            }
         }
 
-        let mut fp_result = self.find_with_suffix(&tree, &[]);
-        fp_result.elimination_sets.extend(elimination_sets);
+        let mut fp_result = FPResult::new(vec![], HashSet::new());
+        let mut capped_sink = CappedSink {
+            sink: &mut fp_result,
+            max_results: self.max_results.unwrap_or(usize::MAX),
+            results_num: 0,
+            is_capped: false,
+        };
+        if self.is_emitted(1) {
+            for &item in frequent_items.iter() {
+                if !self.contains_anchors(&[item]) {
+                    continue;
+                }
+                capped_sink.frequent_pattern(&[item], items[&item]);
+            }
+        }
+        for ((a, b), support) in pair_supports.into_iter() {
+            if self.is_emitted(2)
+                && self.contains_anchors(&[a, b])
+                && self.is_frequent(support, 2, self.minimum_support)
+                && !self.conflicts_with(&a, &[b])
+            {
+                capped_sink.frequent_pattern(&[a, b], support);
+            }
+        }
+        let is_complete = !capped_sink.is_capped;
+        fp_result.is_complete = is_complete;
+        fp_result.item_supports = items;
+        fp_result.transactions_num = self.transactions.transactions_num();
         fp_result
     }
 
-    fn find_with_suffix(&self, tree: &Tree<T>, suffix: &[T]) -> FPResult<T> {
-        let mut fp_result = FPResult::new(vec![], HashSet::new());
-        for (item, nodes) in tree.get_all_items_nodes().iter() {
-            let mut support = 0;
-            for node in nodes.iter() {
-                support += node.count();
-            }
-            let mut frequent_pattern = vec![*item];
-            frequent_pattern.append(&mut Vec::from(suffix));
-            if support >= self.minimum_support && !suffix.contains(item) {
-                fp_result
-                    .frequent_patterns
-                    .push((frequent_pattern.clone(), support));
+    /// Find frequent patterns in the given transactions using FP-Growth, but process at most
+    /// `max_items` items of the header table in the same order as the items in a transaction,
+    /// i.e. the most frequent items first unless [`FPGrowth::with_item_priority()`] is set.
+    /// Since the partial tree of an item only holds the items before it, the patterns found
+    /// are exactly the frequent patterns made up of the processed items. Check
+    /// [`FPResult::is_complete()`] to tell whether every item has been processed.
+    ///
+    /// The result is deterministic for the same `max_items`, and the patterns with the highest
+    /// support, which are made up of the most frequent items, are found first.
+    pub fn find_frequent_patterns_budgeted(&self, max_items: usize) -> FPResult<T> {
+        let items = self.count_items();
+        let (tree, elimination_sets, minimum_support) =
+            self.build_fitting_tree::<Tree<T>>(&items, self.minimum_support);
+        let mut fp_result = FPResult::new(vec![], elimination_sets);
+        let mut item_supports = tree.item_supports();
+        let supports: HashMap<T, usize> = item_supports.iter().copied().collect();
+        let supports: HashMap<&T, &usize> = supports.iter().collect();
+        item_supports.sort_by(|a, b| self.compare_items(&a.0, &b.0, &supports));
+        fp_result.is_complete = item_supports.len() <= max_items;
+        let node_pool = self.new_node_pool();
+        for &(item, support) in item_supports.iter().take(max_items) {
+            self.extend_with_item(
+                &tree,
+                &[],
+                item,
+                support,
+                minimum_support,
+                node_pool.as_ref(),
+                &mut fp_result,
+            );
+        }
+        fp_result.item_supports = items;
+        fp_result.transactions_num = self.transactions.transactions_num();
+        fp_result
+    }
 
-                let partial_tree = Tree::generate_partial_tree(&tree.generate_prefix_path(*item));
-                let mut mid_fp_result = self.find_with_suffix(&partial_tree, &frequent_pattern);
-                fp_result
-                    .frequent_patterns
-                    .append(&mut mid_fp_result.frequent_patterns);
-                fp_result
-                    .elimination_sets
-                    .extend(mid_fp_result.elimination_sets);
-            } else {
-                fp_result.elimination_sets.insert(frequent_pattern);
+    /// Find frequent patterns in the given transactions using FP-Growth,
+    /// return an error instead if the tree exceeds the memory budget.
+    pub fn try_find_frequent_patterns(&self) -> Result<FPResult<T>, Error> {
+        match self.compact_tree {
+            true => self.try_find_frequent_patterns_in::<CompactTree<T>>(),
+            false => self.try_find_frequent_patterns_in::<Tree<T>>(),
+        }
+    }
+
+    /// Find frequent patterns in the given transactions using FP-Growth with the tree `G`,
+    /// return an error instead if the tree exceeds the memory budget.
+    fn try_find_frequent_patterns_in<G: MiningTree<T>>(&self) -> Result<FPResult<T>, Error> {
+        let items = self.count_items();
+        let (tree, elimination_sets) =
+            self.build_tree::<G>(&items, self.minimum_support, &HashSet::new())?;
+        let mut fp_result = FPResult::new(vec![], elimination_sets);
+        fp_result.is_complete =
+            self.find_with_max_results(&tree, self.minimum_support, &mut fp_result);
+        fp_result.item_supports = items;
+        fp_result.transactions_num = self.transactions.transactions_num();
+        Ok(fp_result)
+    }
+
+    /// Find frequent patterns in the given transactions using FP-Growth, but pass every
+    /// elimination set to the `observer` as soon as it occurs rather than storing it in the
+    /// result, so they could be logged or counted without holding them all in memory. The same
+    /// set may be observed more than once, and the result holds no elimination set.
+    pub fn find_frequent_patterns_with_elimination_observer<O: FnMut(&[T])>(
+        &self,
+        mut observer: O,
+    ) -> FPResult<T> {
+        let items = self.count_items();
+        let (tree, minimum_support) =
+            self.build_fitting_tree_untracked::<Tree<T>>(&items, self.minimum_support);
+        // Only report the transactions eliminated from the tree fitting into the memory budget.
+        let is_frequent = |item: &T| {
+            items
+                .get(item)
+                .is_some_and(|&count| count >= minimum_support)
+        };
+        for transaction in self.normalized_transactions() {
+            if !transaction.iter().all(is_frequent) {
+                observer(&transaction);
             }
         }
+        let mut fp_result = FPResult::new(vec![], HashSet::new());
+        let mut sink = ObserverSink {
+            fp_result: &mut fp_result,
+            observer: &mut observer,
+        };
+        let is_complete = self.find_with_max_results(&tree, minimum_support, &mut sink);
+        fp_result.is_complete = is_complete;
+        fp_result.item_supports = items;
+        fp_result.transactions_num = self.transactions.transactions_num();
         fp_result
     }
+
+    /// Find frequent patterns with `new_support` as the minimum support by reusing a `previous`
+    /// result mined from the same transactions with a higher threshold, e.g. when tuning the
+    /// threshold interactively. The patterns in `previous` are carried over rather than collected
+    /// again, only the patterns with a support lower than all the previous ones are collected.
+    ///
+    /// The item supports of `previous` are reused as well if it counts the same number of
+    /// transactions, which saves the counting pass over the transactions. The tree is still
+    /// built and mined again at `new_support`, since the patterns above the previous threshold
+    /// have to be walked through to reach their lower supersets.
+    ///
+    /// If `new_support` is not lower than the previous threshold, the previous patterns are
+    /// just filtered without mining. The previous threshold is taken as the lowest support of
+    /// its patterns, so `previous` should hold all patterns above it, i.e. not be truncated.
+    pub fn remine_with_lower_support(
+        &self,
+        previous: &FPResult<T>,
+        new_support: usize,
+    ) -> FPResult<T> {
+        let known_support = previous
+            .frequent_patterns
+            .iter()
+            .map(|(_, support)| *support)
+            .min()
+            .unwrap_or(usize::MAX);
+        let carried_patterns = previous
+            .frequent_patterns
+            .iter()
+            .filter(|(_, support)| *support >= new_support)
+            .cloned();
+        if new_support >= known_support {
+            let mut fp_result = previous.clone();
+            fp_result.frequent_patterns = carried_patterns.collect();
+            fp_result.support_index = OnceLock::new();
+            return fp_result;
+        }
+
+        let transactions_num = self.transactions.transactions_num();
+        let items = if !previous.item_supports.is_empty()
+            && previous.transactions_num == transactions_num
+        {
+            previous.item_supports.clone()
+        } else {
+            self.count_items()
+        };
+        let (tree, elimination_sets, minimum_support) =
+            self.build_fitting_tree::<Tree<T>>(&items, new_support);
+        let mut fp_result = FPResult::new(vec![], elimination_sets);
+        fp_result.frequent_patterns = carried_patterns.collect();
+        let mut sink = NewPatternSink {
+            fp_result: &mut fp_result,
+            known_support,
+        };
+        self.find_with_suffix(&tree, &[], minimum_support, &mut sink);
+        fp_result.item_supports = items;
+        fp_result.transactions_num = transactions_num;
+        fp_result
+    }
+
+    /// Find the patterns whose support is in the range of `lo..=hi`, e.g. to study the
+    /// mid-frequency band without the ubiquitous and the barely frequent patterns. The configured
+    /// minimum support is ignored, and no pattern is found if `lo` is larger than `hi`.
+    ///
+    /// Only `lo` is used to prune the search space while mining, the patterns whose support is
+    /// larger than `hi` are discarded as they are found, since the upper bound is not
+    /// anti-monotone, i.e. the supersets of a pattern above `hi` could be in the range.
+    pub fn find_patterns_in_support_range(&self, lo: usize, hi: usize) -> FPResult<T> {
+        let items = self.count_items();
+        let (tree, elimination_sets, minimum_support) =
+            self.build_fitting_tree::<Tree<T>>(&items, lo);
+        let mut fp_result = FPResult::new(vec![], elimination_sets);
+        // The patterns above `hi` are treated like the known ones, which are not collected.
+        let mut sink = NewPatternSink {
+            fp_result: &mut fp_result,
+            known_support: hi.saturating_add(1),
+        };
+        self.find_with_suffix(&tree, &[], minimum_support, &mut sink);
+        fp_result.item_supports = items;
+        fp_result.transactions_num = self.transactions.transactions_num();
+        fp_result
+    }
+
+    /// Find frequent patterns in the given transactions using FP-Growth, every frequent
+    /// pattern is sent through `tx` as soon as it is found. It returns once the mining is done.
+    ///
+    /// The mining goes on even if the receiver has been dropped.
+    pub fn mine_to_channel(&self, tx: Sender<(Vec<T>, usize)>) {
+        let items = self.count_items();
+        let (tree, minimum_support) =
+            self.build_fitting_tree_untracked::<Tree<T>>(&items, self.minimum_support);
+        self.find_with_max_results(&tree, minimum_support, &mut ChannelSink(tx));
+    }
+
+    /// Find frequent patterns in the given transactions using FP-Growth, and explain every
+    /// pattern with the paths in the FP-Growth tree its support comes from.
+    ///
+    /// It is heavier than [`FPGrowth::find_frequent_patterns()`] since the paths of every
+    /// pattern need to be collected from the tree.
+    pub fn find_frequent_patterns_explained(&self) -> Vec<PatternExplanation<T>> {
+        let items = self.count_items();
+        let (tree, elimination_sets, minimum_support) =
+            self.build_fitting_tree::<Tree<T>>(&items, self.minimum_support);
+        let mut fp_result = FPResult::new(vec![], elimination_sets);
+        self.find_with_suffix(&tree, &[], minimum_support, &mut fp_result);
+        fp_result
+            .frequent_patterns
+            .into_iter()
+            .map(|(pattern, support)| {
+                // Only the paths ending with the last item of the pattern in the tree contain
+                // all the items, so every contributing path will be collected exactly once.
+                let paths = pattern
+                    .iter()
+                    .flat_map(|&item| tree.generate_prefix_path(item))
+                    .filter(|path| {
+                        pattern
+                            .iter()
+                            .all(|&item| path.iter().any(|node| node.item() == Some(item)))
+                    })
+                    .map(|path| {
+                        path.iter()
+                            .filter_map(|node| node.item().map(|item| (item, node.count())))
+                            .collect()
+                    })
+                    .collect();
+                PatternExplanation {
+                    pattern: pattern.into_vec(),
+                    support,
+                    paths,
+                }
+            })
+            .collect()
+    }
+
+    /// Return the number of frequent patterns under every given threshold as the minimum support,
+    /// in pairs of `(threshold, pattern_count)`.
+    ///
+    /// It builds a single tree with the lowest threshold and mines it only once, so the tree
+    /// could be much larger than the one built with the configured minimum support. If the
+    /// memory budget forces the minimum support to be raised, the thresholds below it are left out.
+    pub fn pattern_count_curve(&self, thresholds: &[usize]) -> Vec<(usize, usize)> {
+        let lowest_threshold = match thresholds.iter().min() {
+            Some(&threshold) => threshold,
+            None => return vec![],
+        };
+        let items = self.count_items();
+        let (tree, minimum_support) =
+            self.build_fitting_tree_untracked::<Tree<T>>(&items, lowest_threshold);
+        let mut fp_result = FPResult::new(vec![], HashSet::new());
+        self.find_with_suffix(&tree, &[], minimum_support, &mut fp_result);
+        thresholds
+            .iter()
+            .filter(|&&threshold| threshold >= minimum_support)
+            .map(|&threshold| {
+                let pattern_count = fp_result
+                    .frequent_patterns
+                    .iter()
+                    .filter(|(_, support)| *support >= threshold)
+                    .count();
+                (threshold, pattern_count)
+            })
+            .collect()
+    }
+
+    /// Find only the closed patterns, which have no superset with the same support, ordered like
+    /// [`FPResult::closed_patterns()`]. Every pattern is checked against the closed ones found
+    /// so far with the same support as soon as it's mined, so only the closed patterns are held
+    /// rather than all frequent patterns, which could be far more on the dense data.
+    pub fn find_closed_patterns(&self) -> Vec<(Vec<T>, usize)> {
+        let items = self.count_items();
+        let (tree, minimum_support) =
+            self.build_fitting_tree_untracked::<Tree<T>>(&items, self.minimum_support);
+        let mut closed_sink = ClosedSink {
+            support_groups: HashMap::new(),
+        };
+        self.find_with_suffix(&tree, &[], minimum_support, &mut closed_sink);
+        let mut closed_patterns: Vec<(Vec<T>, usize)> = closed_sink
+            .support_groups
+            .into_iter()
+            .flat_map(|(support, patterns)| {
+                patterns.into_iter().map(move |pattern| (pattern, support))
+            })
+            .collect();
+        closed_patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        closed_patterns
+    }
+
+    /// Find the `k` closed patterns with the highest support, ordered like
+    /// [`FPResult::closed_patterns()`], the ties at the k-th support are broken lexicographically.
+    /// Fewer patterns are returned only if there are not `k` closed patterns whose support is
+    /// not lower than the minimum support.
+    ///
+    /// It starts mining with the highest item support as the threshold and halves it until `k`
+    /// closed patterns are found, which is correct since the closedness of a pattern only
+    /// depends on its supersets with the same support, which are found under the same threshold.
+    pub fn find_top_k_closed_patterns(&self, k: usize) -> Vec<(Vec<T>, usize)> {
+        if k == 0 {
+            return vec![];
+        }
+        let items = self.count_items();
+        let mut threshold = items
+            .values()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(self.minimum_support);
+        loop {
+            let (tree, minimum_support) =
+                self.build_fitting_tree_untracked::<Tree<T>>(&items, threshold);
+            let mut fp_result = FPResult::new(vec![], HashSet::new());
+            self.find_with_suffix(&tree, &[], minimum_support, &mut fp_result);
+            let mut closed_patterns = fp_result.closed_patterns();
+            // The memory budget may keep the threshold from going any lower.
+            if closed_patterns.len() >= k
+                || threshold <= self.minimum_support
+                || minimum_support > threshold
+            {
+                closed_patterns.truncate(k);
+                return closed_patterns;
+            }
+            threshold = (threshold / 2).max(self.minimum_support);
+        }
+    }
+
+    /// Find the `k` frequent patterns with the highest support, ordered like
+    /// [`FPResult::closed_patterns()`], the ties at the k-th support are broken lexicographically.
+    /// Fewer patterns are returned only if there are not `k` frequent patterns at all.
+    ///
+    /// The minimum support is raised to the lowest support of the best `k` patterns found so
+    /// far as soon as there are `k` of them, so the partial trees which could not hold a better
+    /// pattern are never mined. It starts from the k-th highest item support if the single-item
+    /// patterns are in the output, since there are `k` patterns at least as frequent as it.
+    pub fn find_top_k_patterns(&self, k: usize) -> Vec<(Vec<T>, usize)> {
+        if k == 0 {
+            return vec![];
+        }
+        let items = self.count_items();
+        let mut minimum_support = self.minimum_support;
+        if self.is_emitted(1) && self.anchor_items.is_empty() && self.support_predicate.is_none() {
+            let mut supports: Vec<usize> = items.values().copied().collect();
+            if supports.len() >= k {
+                supports.sort_unstable_by(|a, b| b.cmp(a));
+                minimum_support = minimum_support.max(supports[k - 1]);
+            }
+        }
+        let (tree, minimum_support) =
+            self.build_fitting_tree_untracked::<Tree<T>>(&items, minimum_support);
+        let mut top_k_sink = TopKSink {
+            k,
+            patterns: BinaryHeap::new(),
+        };
+        self.find_with_suffix(&tree, &[], minimum_support, &mut top_k_sink);
+        let mut patterns: Vec<(Vec<T>, usize)> = top_k_sink
+            .patterns
+            .into_iter()
+            .map(|(Reverse(support), pattern)| (pattern, support))
+            .collect();
+        patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        patterns
+    }
+
+    /// Find only the maximal patterns, which have no frequent superset, ordered like
+    /// [`FPResult::closed_patterns()`]. It's the FPMax algorithm, which mines the least frequent
+    /// items first so a maximal pattern is always found before its subsets, and skips mining a
+    /// partial tree if its suffix with all its frequent items is a subset of a maximal pattern
+    /// found before. The subsets of the maximal patterns are never emitted.
+    pub fn find_maximal_patterns(&self) -> Vec<(Vec<T>, usize)> {
+        let items = self.count_items();
+        let (tree, minimum_support) =
+            self.build_fitting_tree_untracked::<Tree<T>>(&items, self.minimum_support);
+        // The items of the trees are in the same order as they are in the transactions.
+        let item_supports: HashMap<T, usize> = tree.item_supports().into_iter().collect();
+        let supports: HashMap<&T, &usize> = item_supports.iter().collect();
+        let mut ordered_items: Vec<T> = item_supports.keys().copied().collect();
+        ordered_items.sort_by(|a, b| self.compare_items(a, b, &supports));
+        let item_ranks: HashMap<T, usize> = ordered_items
+            .into_iter()
+            .enumerate()
+            .map(|(rank, item)| (item, rank))
+            .collect();
+
+        let mut maximal_patterns = MaximalPatterns {
+            patterns: vec![],
+            item_patterns: HashMap::new(),
+        };
+        self.find_maximal_with_suffix(
+            &tree,
+            &[],
+            0,
+            minimum_support,
+            &item_ranks,
+            self.new_node_pool().as_ref(),
+            &mut maximal_patterns,
+        );
+        let mut maximal_patterns = maximal_patterns.patterns;
+        // A maximal pattern containing the anchor items is still maximal among the patterns
+        // containing them, since all its supersets contain them as well.
+        maximal_patterns.retain(|(pattern, _)| self.contains_anchors(pattern));
+        maximal_patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        maximal_patterns
+    }
+
+    /// Return the number of the maximal frequent patterns, which have no frequent superset.
+    ///
+    /// All the frequent patterns are mined and held first. By the downward closure, a pattern
+    /// is maximal if none of its one-item extensions is frequent, so every pattern is checked
+    /// against the frequent items, and only the maximal ones are counted rather than collected.
+    pub fn maximal_pattern_count(&self) -> usize {
+        let (fp_result, frequent_items) = self.mine_with_frequent_items();
+        count_maximal_patterns(&fp_result, &frequent_items)
+    }
+
+    /// Return the numbers of all, closed and maximal frequent patterns, which tell how redundant
+    /// the frequent patterns are. The transactions are mined only once for all of them.
+    pub fn pattern_compression_stats(&self) -> PatternStats {
+        let (fp_result, frequent_items) = self.mine_with_frequent_items();
+        PatternStats {
+            all_count: fp_result.frequent_patterns_num(),
+            closed_count: fp_result.closed_patterns().len(),
+            maximal_count: count_maximal_patterns(&fp_result, &frequent_items),
+        }
+    }
+
+    /// Return the data-quality report of the transactions, which are normalized and augmented
+    /// as they are mined. It takes a single pass over the transactions without mining them.
+    pub fn input_report(&self) -> InputReport<T> {
+        let mut transactions_num = 0;
+        let mut empty_count = 0;
+        let mut min_len = usize::MAX;
+        let mut max_len = 0;
+        let mut total_len = 0;
+        let mut distinct_transactions: HashSet<Vec<T>> = HashSet::new();
+        let mut item_supports: HashMap<T, usize> = HashMap::new();
+        for transaction in self.normalized_transactions() {
+            let mut transaction = transaction.into_owned();
+            transaction.sort_unstable();
+            transaction.dedup();
+            transactions_num += 1;
+            if transaction.is_empty() {
+                empty_count += 1;
+            }
+            min_len = min_len.min(transaction.len());
+            max_len = max_len.max(transaction.len());
+            total_len += transaction.len();
+            for &item in transaction.iter() {
+                *item_supports.entry(item).or_default() += 1;
+            }
+            distinct_transactions.insert(transaction);
+        }
+        let mut singleton_items: Vec<T> = item_supports
+            .into_iter()
+            .filter(|&(_, support)| support == 1)
+            .map(|(item, _)| item)
+            .collect();
+        singleton_items.sort_unstable();
+        InputReport {
+            transactions_num,
+            empty_count,
+            duplicate_count: transactions_num - distinct_transactions.len(),
+            min_len: if transactions_num == 0 { 0 } else { min_len },
+            max_len,
+            avg_len: match transactions_num {
+                0 => 0.0,
+                _ => total_len as f64 / transactions_num as f64,
+            },
+            singleton_items,
+        }
+    }
+
+    /// Mine the frequent patterns without the elimination sets, the frequent items are returned
+    /// as well.
+    fn mine_with_frequent_items(&self) -> (FPResult<T>, Vec<T>) {
+        let items = self.count_items();
+        let (tree, minimum_support) =
+            self.build_fitting_tree_untracked::<Tree<T>>(&items, self.minimum_support);
+        let mut fp_result = FPResult::new(vec![], HashSet::new());
+        self.find_with_suffix(&tree, &[], minimum_support, &mut fp_result);
+        let frequent_items = items
+            .into_iter()
+            .filter(|(_, count)| *count >= minimum_support)
+            .map(|(item, _)| item)
+            .collect();
+        (fp_result, frequent_items)
+    }
+
+    /// Return the number of distinct items over all transactions, the items whose support is
+    /// lower than the minimum support are counted as well.
+    pub fn distinct_item_count(&self) -> usize {
+        self.count_items().len()
+    }
+
+    /// Estimate an upper bound of the number of frequent patterns without mining, e.g. to tell
+    /// a mining producing billions of patterns before running it. It's only an estimate, which
+    /// is usually far larger than the exact number, but never smaller.
+    ///
+    /// Every frequent pattern is a subset of the path from the root to some leaf of the tree,
+    /// so the estimate is the number of non-empty subsets of every path, up to the maximum
+    /// depth if it's set, bounded by the number of the subsets of all frequent items.
+    /// It saturates at `usize::MAX`.
+    pub fn estimate_pattern_count(&self) -> usize {
+        let items = self.count_items();
+        let (tree, _) = self.build_fitting_tree_untracked::<Tree<T>>(&items, self.minimum_support);
+        let subset_count = |len: usize| -> usize {
+            let max_len = self.max_depth.map_or(len, |max_depth| max_depth.min(len));
+            // Sum up the binomial coefficients C(len, k) for k in 1..=max_len.
+            let mut binomial: usize = 1;
+            let mut count: usize = 0;
+            for k in 1..=max_len {
+                binomial = match binomial.checked_mul(len + 1 - k) {
+                    Some(product) => product / k,
+                    None => return usize::MAX,
+                };
+                count = count.saturating_add(binomial);
+            }
+            count
+        };
+        let path_bound = tree.leaf_depths().into_iter().fold(0usize, |bound, depth| {
+            bound.saturating_add(subset_count(depth))
+        });
+        let frequent_items_num = tree.item_supports().len();
+        path_bound.min(subset_count(frequent_items_num))
+    }
+
+    /// Return the number of the transactions containing all items of the given `itemset`, the
+    /// transactions are normalized and augmented as they are mined.
+    ///
+    /// The first query builds an inverted index from every item to the bitset of the
+    /// transactions containing it, which takes a word per 64 transactions for every item. The
+    /// later queries intersect the bitsets of the items rather than scanning the transactions.
+    pub fn transactions_containing(&self, itemset: &[T]) -> usize {
+        let inverted_index = self.inverted_index.get_or_init(|| {
+            // The number of transactions is only a hint, a bitset grows to hold every
+            // transaction actually read, and ends at the last transaction containing its item.
+            let words_num = self.transactions.transactions_num().div_ceil(64);
+            let mut inverted_index: HashMap<T, Vec<u64>> = HashMap::new();
+            for (index, transaction) in self.normalized_transactions().enumerate() {
+                let word_index = index / 64;
+                for &item in transaction.iter() {
+                    let bitset = inverted_index
+                        .entry(item)
+                        .or_insert_with(|| Vec::with_capacity(words_num));
+                    if bitset.len() <= word_index {
+                        bitset.resize(word_index + 1, 0);
+                    }
+                    bitset[word_index] |= 1 << (index % 64);
+                }
+            }
+            inverted_index
+        });
+        let (first_item, rest_items) = match itemset.split_first() {
+            Some(items) => items,
+            None => return self.transactions.transactions_num(),
+        };
+        let mut bitset = match inverted_index.get(first_item) {
+            Some(bitset) => bitset.clone(),
+            None => return 0,
+        };
+        for item in rest_items.iter() {
+            match inverted_index.get(item) {
+                Some(item_bitset) => bitset
+                    .iter_mut()
+                    .zip(item_bitset.iter())
+                    .for_each(|(word, item_word)| *word &= item_word),
+                None => return 0,
+            }
+        }
+        bitset.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Return the fraction of the transactions whose items are all frequent, i.e. no item of them
+    /// is dropped before building the tree. A low fraction warns that the minimum support
+    /// discards most of the data. It is 1.0 if there is no transaction, since nothing is dropped.
+    pub fn fully_retained_fraction(&self) -> f64 {
+        let items = self.count_items();
+        let is_frequent = |item: &T| {
+            items
+                .get(item)
+                .is_some_and(|&count| count >= self.minimum_support)
+        };
+        let (mut retained_num, mut transactions_num) = (0, 0);
+        for transaction in self.normalized_transactions() {
+            transactions_num += 1;
+            if transaction.iter().all(is_frequent) {
+                retained_num += 1;
+            }
+        }
+        match transactions_num {
+            0 => 1.0,
+            _ => retained_num as f64 / transactions_num as f64,
+        }
+    }
+
+    /// Return the frequent items and their co-occurrence matrix, whose entry `[i][j]` is the
+    /// number of transactions containing both the i-th and the j-th item. The matrix is
+    /// symmetric, and its diagonal holds the supports of the items. The items are ordered as
+    /// they are in the tree, i.e. the most frequent items first unless the priority is set.
+    pub fn cooccurrence_matrix(&self) -> (Vec<T>, Vec<Vec<usize>>) {
+        let items = self.count_items();
+        let frequent_items: HashMap<&T, &usize> = items
+            .iter()
+            .filter(|(_, &count)| count >= self.minimum_support)
+            .collect();
+        let mut ordered_items: Vec<T> = frequent_items.keys().map(|&&item| item).collect();
+        ordered_items.sort_by(|a, b| self.compare_items(a, b, &frequent_items));
+        let indexes: HashMap<T, usize> = ordered_items
+            .iter()
+            .enumerate()
+            .map(|(index, &item)| (item, index))
+            .collect();
+
+        let mut matrix = vec![vec![0; ordered_items.len()]; ordered_items.len()];
+        let mut transaction_indexes: Vec<usize> = vec![];
+        for transaction in self.normalized_transactions() {
+            transaction_indexes.clear();
+            transaction_indexes.extend(transaction.iter().filter_map(|item| indexes.get(item)));
+            transaction_indexes.sort_unstable();
+            transaction_indexes.dedup();
+            for (position, &i) in transaction_indexes.iter().enumerate() {
+                for &j in transaction_indexes[position..].iter() {
+                    matrix[i][j] += 1;
+                    if i != j {
+                        matrix[j][i] += 1;
+                    }
+                }
+            }
+        }
+        (ordered_items, matrix)
+    }
+
+    /// Build the FP-Growth tree with `minimum_support` which fits into the memory budget, the
+    /// minimum support will be raised if necessary and returned with the tree and its elimination sets.
+    fn build_fitting_tree<G: MiningTree<T>>(
+        &self,
+        items: &HashMap<T, usize>,
+        minimum_support: usize,
+    ) -> (G, HashSet<Vec<T>>, usize) {
+        self.build_fitting_tree_filtered(items, minimum_support, &HashSet::new())
+    }
+
+    /// Build the FP-Growth tree which fits into the memory budget like `build_fitting_tree`,
+    /// but with the transactions holding none of the `skipped_items`.
+    fn build_fitting_tree_filtered<G: MiningTree<T>>(
+        &self,
+        items: &HashMap<T, usize>,
+        minimum_support: usize,
+        skipped_items: &HashSet<T>,
+    ) -> (G, HashSet<Vec<T>>, usize) {
+        let mut minimum_support = minimum_support;
+        loop {
+            match self.build_tree(items, minimum_support, skipped_items) {
+                Ok((tree, elimination_sets)) => return (tree, elimination_sets, minimum_support),
+                Err(_) => match self.raise_minimum_support(items, minimum_support, skipped_items) {
+                    Some(raised_minimum_support) => minimum_support = raised_minimum_support,
+                    None => return (G::with_expected_items(0), HashSet::new(), minimum_support),
+                },
+            }
+        }
+    }
+
+    /// Build the FP-Growth tree which fits into the memory budget like `build_fitting_tree`,
+    /// but without collecting the elimination sets.
+    fn build_fitting_tree_untracked<G: MiningTree<T>>(
+        &self,
+        items: &HashMap<T, usize>,
+        minimum_support: usize,
+    ) -> (G, usize) {
+        let mut minimum_support = minimum_support;
+        loop {
+            match self.build_tree_with(items, minimum_support, &HashSet::new(), &mut |_| {}) {
+                Ok(tree) => return (tree, minimum_support),
+                Err(_) => match self.raise_minimum_support(items, minimum_support, &HashSet::new())
+                {
+                    Some(raised_minimum_support) => minimum_support = raised_minimum_support,
+                    None => return (G::with_expected_items(0), minimum_support),
+                },
+            }
+        }
+    }
+
+    /// Raise the `minimum_support` to drop the least frequent item still in the tree, which is
+    /// either one of the `items` or the other bucket. `None` is returned if there is no such
+    /// item, then the tree is already empty. Since the minimum support always goes up and no
+    /// support is higher than the number of transactions, raising it again and again will end.
+    fn raise_minimum_support(
+        &self,
+        items: &HashMap<T, usize>,
+        minimum_support: usize,
+        skipped_items: &HashSet<T>,
+    ) -> Option<usize> {
+        items
+            .values()
+            .copied()
+            .chain(self.other_bucket_support(items, minimum_support, skipped_items))
+            .filter(|&count| count >= minimum_support)
+            .min()
+            .map(|count| count + 1)
+    }
+
+    /// Return the support of the other bucket with `minimum_support` if it's set, which is the
+    /// number of the transactions with any item less frequent than `minimum_support`, among the
+    /// ones holding none of the `skipped_items`.
+    fn other_bucket_support(
+        &self,
+        items: &HashMap<T, usize>,
+        minimum_support: usize,
+        skipped_items: &HashSet<T>,
+    ) -> Option<usize> {
+        self.other_item?;
+        let is_frequent = |item: &T| {
+            items
+                .get(item)
+                .is_some_and(|&count| count >= minimum_support)
+        };
+        Some(
+            self.weighted_normalized_transactions(skipped_items)
+                .filter(|(transaction, _)| !transaction.iter().all(is_frequent))
+                .map(|(_, count)| count)
+                .sum(),
+        )
+    }
+
+    /// Count the support of every item in the transactions.
+    fn count_items(&self) -> HashMap<T, usize> {
+        self.count_items_filtered(&HashSet::new())
+    }
+
+    /// Count the support of every item in the transactions holding none of the `skipped_items`.
+    fn count_items_filtered(&self, skipped_items: &HashSet<T>) -> HashMap<T, usize> {
+        if skipped_items.is_empty()
+            && self.item_normalizer.is_none()
+            && self.excluded_items.is_empty()
+            && self.item_exclusion.is_none()
+            && self.item_ancestors.is_empty()
+        {
+            return self.transactions.item_supports();
+        }
+        count_weighted_item_supports(self.weighted_normalized_transactions(skipped_items))
+    }
+
+    /// Return an iterator over the transactions with every item normalized, and augmented with
+    /// the ancestors of its items if the item hierarchy is set.
+    fn normalized_transactions(&self) -> Box<dyn Iterator<Item = Cow<'_, [T]>> + '_> {
+        Box::new(
+            self.transactions
+                .transactions()
+                .map(move |transaction| self.normalize(transaction)),
+        )
+    }
+
+    /// Return an iterator over the normalized transactions with the numbers of their
+    /// occurrences, see [`TransactionSource::weighted_transactions()`]. The transactions holding
+    /// any of the `skipped_items` are skipped before being normalized.
+    fn weighted_normalized_transactions<'a>(
+        &'a self,
+        skipped_items: &'a HashSet<T>,
+    ) -> Box<dyn Iterator<Item = (Cow<'a, [T]>, usize)> + 'a> {
+        Box::new(
+            self.transactions
+                .weighted_transactions()
+                .filter(move |(transaction, _)| {
+                    skipped_items.is_empty()
+                        || !transaction.iter().any(|item| skipped_items.contains(item))
+                })
+                .map(move |(transaction, count)| (self.normalize(transaction), count)),
+        )
+    }
+
+    /// Map every item of the transaction by the item normalizer, strip the excluded items, and
+    /// augment it with the ancestors of its items if the item hierarchy is set.
+    fn normalize<'a>(&self, transaction: Cow<'a, [T]>) -> Cow<'a, [T]> {
+        let transaction = match &self.item_normalizer {
+            Some(item_normalizer) => Cow::Owned(
+                transaction
+                    .iter()
+                    .map(|&item| item_normalizer(item))
+                    .collect(),
+            ),
+            None => transaction,
+        };
+        let transaction = match self.excluded_items.is_empty() && self.item_exclusion.is_none() {
+            true => transaction,
+            false => Cow::Owned(
+                transaction
+                    .iter()
+                    .filter(|item| !self.is_excluded(item))
+                    .copied()
+                    .collect(),
+            ),
+        };
+        if self.item_ancestors.is_empty() {
+            return transaction;
+        }
+        let mut augmented_transaction = transaction.into_owned();
+        for index in 0..augmented_transaction.len() {
+            if let Some(ancestors) = self.item_ancestors.get(&augmented_transaction[index]) {
+                augmented_transaction.extend(
+                    ancestors
+                        .iter()
+                        .filter(|ancestor| !self.is_excluded(ancestor)),
+                );
+            }
+        }
+        augmented_transaction.sort();
+        augmented_transaction.dedup();
+        Cow::Owned(augmented_transaction)
+    }
+
+    /// Check whether the normalized `item` is excluded from the mining.
+    fn is_excluded(&self, item: &T) -> bool {
+        self.excluded_items.contains(item)
+            || self
+                .item_exclusion
+                .as_ref()
+                .is_some_and(|item_exclusion| item_exclusion(item))
+    }
+
+    /// Build the FP-Growth tree with the items whose support is not lower than `minimum_support`,
+    /// the transactions which have items eliminated are returned as well.
+    fn build_tree<G: MiningTree<T>>(
+        &self,
+        items: &HashMap<T, usize>,
+        minimum_support: usize,
+        skipped_items: &HashSet<T>,
+    ) -> Result<(G, HashSet<Vec<T>>), Error> {
+        let mut elimination_sets = HashSet::new();
+        let tree =
+            self.build_tree_with(items, minimum_support, skipped_items, &mut |transaction| {
+                elimination_sets.insert(transaction.into_owned());
+            })?;
+        Ok((tree, elimination_sets))
+    }
+
+    /// Build the FP-Growth tree with the items whose support is not lower than `minimum_support`
+    /// from the transactions holding none of the `skipped_items`, every transaction which has
+    /// items eliminated is passed to `on_eliminated`.
+    fn build_tree_with<G: MiningTree<T>>(
+        &self,
+        items: &HashMap<T, usize>,
+        minimum_support: usize,
+        skipped_items: &HashSet<T>,
+        on_eliminated: &mut dyn FnMut(Cow<'_, [T]>),
+    ) -> Result<G, Error> {
+        let other_bucket =
+            self.other_item
+                .zip(self.other_bucket_support(items, minimum_support, skipped_items));
+        // Clean up the items whose support is lower than the minimum_support.
+        let mut cleaned_items: HashMap<&T, &usize> = items
+            .iter()
+            .filter(|(_, &count)| count >= minimum_support)
+            .collect();
+        if let Some((other_item, count)) = &other_bucket {
+            if *count >= minimum_support {
+                cleaned_items.insert(other_item, count);
+            }
+        }
+
+        let mut tree = G::with_expected_items(cleaned_items.len());
+        for (transaction, count) in self.weighted_normalized_transactions(skipped_items) {
+            let mut cleaned_transaction: Vec<T> = transaction
+                .iter()
+                .filter(|item| cleaned_items.contains_key(item))
+                .copied()
+                .collect();
+            if cleaned_transaction.len() != transaction.len() {
+                if let Some((other_item, _)) = other_bucket {
+                    if cleaned_items.contains_key(&other_item) {
+                        cleaned_transaction.push(other_item);
+                    }
+                }
+                on_eliminated(transaction);
+            }
+            cleaned_transaction.sort_by(|a, b| self.compare_items(a, b, &cleaned_items));
+            // After sort cleaned_transaction, remove consecutive items from it then.
+            cleaned_transaction.dedup();
+            tree.add_transaction_with_count(cleaned_transaction, count);
+            if let Some(max_nodes) = self.max_nodes {
+                if tree.node_count() > max_nodes {
+                    return Err(Error::MemoryBudgetExceeded { max_nodes });
+                }
+            }
+        }
+
+        Ok(tree)
+    }
+
+    /// Compare two items to decide their order in a transaction, the items with a higher priority
+    /// or support come first. When both are the same, we will sort by T itself.
+    /// e.g. ["c", "b", "a"] -> ["a", "b", "c"]
+    fn compare_items(&self, a: &T, b: &T, items: &HashMap<&T, &usize>) -> Ordering {
+        let priority = |item| self.item_priority.get(item);
+        self.anchor_items
+            .contains(a)
+            .cmp(&self.anchor_items.contains(b))
+            .then_with(|| priority(b).cmp(&priority(a)))
+            .then_with(|| items.get(b).cmp(&items.get(a)))
+            .then_with(|| a.cmp(b))
+    }
+
+    /// Check whether the `item` is in the same mutex group with any item of the `suffix`, or it
+    /// is an ancestor or a descendant of any item of the `suffix` in the item hierarchy.
+    fn conflicts_with(&self, item: &T, suffix: &[T]) -> bool {
+        let is_ancestor = |ancestor: &T, descendant: &T| {
+            self.item_ancestors
+                .get(descendant)
+                .is_some_and(|ancestors| ancestors.contains(ancestor))
+        };
+        self.mutex_groups
+            .iter()
+            .any(|group| group.contains(item) && suffix.iter().any(|other| group.contains(other)))
+            || suffix
+                .iter()
+                .any(|other| is_ancestor(item, other) || is_ancestor(other, item))
+    }
+
+    /// Check whether a frequent pattern with `pattern_len` items is in the output.
+    fn is_emitted(&self, pattern_len: usize) -> bool {
+        pattern_len >= self.min_length && (self.include_singletons || pattern_len > 1)
+    }
+
+    /// Check whether the `pattern` contains all the anchor items.
+    fn contains_anchors(&self, pattern: &[T]) -> bool {
+        self.anchor_items
+            .iter()
+            .all(|anchor_item| pattern.contains(anchor_item))
+    }
+
+    /// Check whether a pattern with `pattern_len` items could not be extended any more.
+    fn reach_max_depth(&self, pattern_len: usize) -> bool {
+        self.max_depth
+            .is_some_and(|max_depth| pattern_len >= max_depth)
+    }
+
+    /// Check whether a pattern with `pattern_len` items and the `support` is frequent, by the
+    /// support predicate if it's set or by the `minimum_support` otherwise.
+    fn is_frequent(&self, support: usize, pattern_len: usize, minimum_support: usize) -> bool {
+        match &self.support_predicate {
+            Some(support_predicate) => support_predicate.is_frequent(support, pattern_len),
+            None => support >= minimum_support,
+        }
+    }
+
+    /// Mine the `tree` from the empty suffix, but stop once the maximum number of results have
+    /// been emitted if it's set. Return whether the mining ran to the end.
+    fn find_with_max_results<G: MiningTree<T>>(
+        &self,
+        tree: &G,
+        minimum_support: usize,
+        sink: &mut dyn PatternSink<T>,
+    ) -> bool {
+        match self.max_results {
+            Some(max_results) => {
+                let mut capped_sink = CappedSink {
+                    sink,
+                    max_results,
+                    results_num: 0,
+                    is_capped: false,
+                };
+                self.find_with_suffix(tree, &[], minimum_support, &mut capped_sink);
+                !capped_sink.is_capped
+            }
+            None => {
+                self.find_with_suffix(tree, &[], minimum_support, sink);
+                true
+            }
+        }
+    }
+
+    /// Mine the `tree` from the `suffix` with a node pool of its own if it's enabled.
+    fn find_with_suffix<G: MiningTree<T>>(
+        &self,
+        tree: &G,
+        suffix: &[T],
+        minimum_support: usize,
+        sink: &mut dyn PatternSink<T>,
+    ) {
+        let node_pool = self.new_node_pool();
+        self.find_with_suffix_in(tree, suffix, minimum_support, node_pool.as_ref(), sink);
+    }
+
+    /// Create a node pool for a mining if it's enabled by [`FPGrowth::with_node_pool()`].
+    fn new_node_pool(&self) -> Option<RefCell<NodePool<T>>> {
+        self.use_node_pool.then(|| RefCell::new(NodePool::new()))
+    }
+
+    /// Mine the `tree` from the `suffix`, the partial trees take their nodes from the
+    /// `node_pool` if it's given.
+    fn find_with_suffix_in<G: MiningTree<T>>(
+        &self,
+        tree: &G,
+        suffix: &[T],
+        minimum_support: usize,
+        node_pool: Option<&RefCell<NodePool<T>>>,
+        sink: &mut dyn PatternSink<T>,
+    ) {
+        if self.reach_max_depth(suffix.len()) {
+            return;
+        }
+        for (item, support) in tree.item_supports() {
+            if sink.is_full() {
+                return;
+            }
+            self.extend_with_item(
+                tree,
+                suffix,
+                item,
+                support,
+                minimum_support,
+                node_pool,
+                sink,
+            );
+        }
+    }
+
+    /// Find the maximal patterns extended from the `suffix` of `suffix_support` in its partial
+    /// `tree`, the ones not subsumed by the `maximal_patterns` found before are added to them.
+    #[allow(clippy::too_many_arguments)]
+    fn find_maximal_with_suffix(
+        &self,
+        tree: &Tree<T>,
+        suffix: &[T],
+        suffix_support: usize,
+        minimum_support: usize,
+        item_ranks: &HashMap<T, usize>,
+        node_pool: Option<&RefCell<NodePool<T>>>,
+        maximal_patterns: &mut MaximalPatterns<T>,
+    ) {
+        let mut frequent_items: Vec<(T, usize)> = match self.reach_max_depth(suffix.len()) {
+            true => vec![],
+            false => MiningTree::item_supports(tree)
+                .into_iter()
+                // The partial tree of the suffix holds its first item as well.
+                .filter(|&(item, support)| {
+                    self.is_frequent(support, suffix.len() + 1, minimum_support)
+                        && !suffix.contains(&item)
+                        && !self.conflicts_with(&item, suffix)
+                })
+                .collect(),
+        };
+        if frequent_items.is_empty() {
+            let mut pattern = suffix.to_vec();
+            pattern.sort();
+            if !pattern.is_empty() && !maximal_patterns.subsumes(&pattern) {
+                maximal_patterns.insert(pattern, suffix_support);
+            }
+            return;
+        }
+
+        // Mine the least frequent items first, whose partial trees hold the other items.
+        frequent_items.sort_by_key(|(item, _)| Reverse(item_ranks.get(item)));
+        for (item, support) in frequent_items.into_iter() {
+            let mut pattern = vec![item];
+            pattern.extend_from_slice(suffix);
+            let partial_tree = tree.partial_tree(item, node_pool);
+            // Skip the partial tree if nothing found in it could be maximal.
+            let mut candidate = pattern.clone();
+            candidate.extend(
+                MiningTree::item_supports(&partial_tree)
+                    .into_iter()
+                    .filter(|&(item, support)| {
+                        self.is_frequent(support, pattern.len() + 1, minimum_support)
+                            && !pattern.contains(&item)
+                    })
+                    .map(|(item, _)| item),
+            );
+            candidate.sort();
+            if !maximal_patterns.subsumes(&candidate) {
+                self.find_maximal_with_suffix(
+                    &partial_tree,
+                    &pattern,
+                    support,
+                    minimum_support,
+                    item_ranks,
+                    node_pool,
+                    maximal_patterns,
+                );
+            }
+            MiningTree::recycle(partial_tree, node_pool);
+        }
+    }
+
+    /// Extend the `suffix` with the `item` whose `support` is counted in the `tree`, emit the
+    /// new pattern if it's frequent and go on mining its partial tree, which takes its nodes
+    /// from the `node_pool` if it's given.
+    #[allow(clippy::too_many_arguments)]
+    fn extend_with_item<G: MiningTree<T>>(
+        &self,
+        tree: &G,
+        suffix: &[T],
+        item: T,
+        support: usize,
+        minimum_support: usize,
+        node_pool: Option<&RefCell<NodePool<T>>>,
+        sink: &mut dyn PatternSink<T>,
+    ) {
+        // Any pattern extended from a conflicting one conflicts as well.
+        if self.conflicts_with(&item, suffix) {
+            return;
+        }
+        // The anchor items are ordered last, so a pattern containing them all is extended from
+        // them before any other item.
+        if !self.anchor_items.contains(&item) && !self.contains_anchors(suffix) {
+            return;
+        }
+        let minimum_support = minimum_support.max(sink.minimum_support());
+        let mut frequent_pattern = vec![item];
+        frequent_pattern.append(&mut Vec::from(suffix));
+        if self.is_frequent(support, frequent_pattern.len(), minimum_support)
+            && !suffix.contains(&item)
+        {
+            if self.is_emitted(frequent_pattern.len()) && self.contains_anchors(&frequent_pattern) {
+                sink.frequent_pattern(&frequent_pattern, support);
+            }
+            if self.reach_max_depth(frequent_pattern.len()) {
+                return;
+            }
+
+            let partial_tree = tree.partial_tree(item, node_pool);
+            self.find_with_suffix_in(
+                &partial_tree,
+                &frequent_pattern,
+                minimum_support,
+                node_pool,
+                sink,
+            );
+            partial_tree.recycle(node_pool);
+        } else {
+            // Sort the candidate, so the same set reached in different orders is only kept once.
+            frequent_pattern.sort();
+            sink.elimination_set(frequent_pattern);
+        }
+    }
+}
+
+/// `WindowMiner<'a, T, S, I>` mines the frequent patterns of a sliding window over a stream of
+/// transactions, see [`FPGrowth::window_miner()`]. It keeps a live tree of the transactions in
+/// the window, whose items are sorted by themselves, so a transaction could be added and
+/// removed without rebuilding the tree.
+pub struct WindowMiner<'a, T, S, I> {
+    fp_growth: &'a FPGrowth<T, S>,
+    stream: I,
+    window: VecDeque<Vec<T>>,
+    window_size: usize,
+    step: usize,
+    tree: Tree<T>,
+    is_started: bool,
+}
+
+impl<T: ItemType, S: TransactionSource<T>, I: Iterator<Item = Vec<T>>> Iterator
+    for WindowMiner<'_, T, S, I>
+{
+    type Item = FPResult<T>;
+
+    /// Ingest the next transactions, expire the ones out of the window and mine the window.
+    /// `None` is returned once the stream has no more transaction.
+    fn next(&mut self) -> Option<FPResult<T>> {
+        let batch_size = match self.is_started {
+            true => self.step,
+            false => self.window_size,
+        };
+        self.is_started = true;
+        let mut ingested_num = 0;
+        while ingested_num < batch_size.max(1) {
+            let transaction = match self.stream.next() {
+                Some(transaction) => transaction,
+                None => break,
+            };
+            let mut transaction = self
+                .fp_growth
+                .normalize(Cow::Owned(transaction))
+                .into_owned();
+            // The anchor items are ordered last like they are in the other trees.
+            let anchor_items = &self.fp_growth.anchor_items;
+            transaction.sort_by_key(|item| (anchor_items.contains(item), *item));
+            transaction.dedup();
+            self.tree.add_transaction(transaction.clone());
+            self.window.push_back(transaction);
+            ingested_num += 1;
+        }
+        if ingested_num == 0 {
+            return None;
+        }
+        while self.window.len() > self.window_size {
+            if let Some(expired_transaction) = self.window.pop_front() {
+                self.tree.remove_transaction(expired_transaction);
+            }
+        }
+
+        let minimum_support = self.fp_growth.minimum_support;
+        let mut fp_result = FPResult::new(vec![], HashSet::new());
+        fp_result.is_complete =
+            self.fp_growth
+                .find_with_max_results(&self.tree, minimum_support, &mut fp_result);
+        fp_result.item_supports = self.tree.item_supports().into_iter().collect();
+        fp_result.transactions_num = self.window.len();
+        Some(fp_result)
+    }
+}
+
+/// `MiningTree<T>` abstracts the FP-Growth trees which could be built and mined by [`FPGrowth`].
+trait MiningTree<T: ItemType>: Sized {
+    fn with_expected_items(expected_items: usize) -> Self;
+
+    fn add_transaction_with_count(&mut self, transaction: Vec<T>, count: usize);
+
+    fn node_count(&self) -> usize;
+
+    /// Return every item in the tree with its support.
+    fn item_supports(&self) -> Vec<(T, usize)>;
+
+    /// Generate the partial tree of the prefix paths ending with the `item`, the nodes are
+    /// taken from the `node_pool` first if it's given.
+    fn partial_tree(&self, item: T, node_pool: Option<&RefCell<NodePool<T>>>) -> Self;
+
+    /// Drop the tree, its nodes are put into the `node_pool` if it's given.
+    fn recycle(self, _node_pool: Option<&RefCell<NodePool<T>>>) {}
+}
+
+impl<T: ItemType> MiningTree<T> for Tree<T> {
+    fn with_expected_items(expected_items: usize) -> Self {
+        Tree::with_expected_items(expected_items)
+    }
+
+    fn add_transaction_with_count(&mut self, transaction: Vec<T>, count: usize) {
+        Tree::add_transaction_with_count(self, transaction, count)
+    }
+
+    fn node_count(&self) -> usize {
+        Tree::node_count(self)
+    }
+
+    fn item_supports(&self) -> Vec<(T, usize)> {
+        Tree::item_supports(self)
+    }
+
+    fn partial_tree(&self, item: T, node_pool: Option<&RefCell<NodePool<T>>>) -> Self {
+        let prefix_paths = self.generate_prefix_path_items(item);
+        match node_pool {
+            Some(node_pool) => Tree::generate_partial_tree_from_items_in(
+                &prefix_paths,
+                &mut node_pool.borrow_mut(),
+            ),
+            None => Tree::generate_partial_tree_from_items(&prefix_paths),
+        }
+    }
+
+    fn recycle(self, node_pool: Option<&RefCell<NodePool<T>>>) {
+        if let Some(node_pool) = node_pool {
+            Tree::recycle(self, &mut node_pool.borrow_mut());
+        }
+    }
+}
+
+impl<T: ItemType> MiningTree<T> for CompactTree<T> {
+    fn with_expected_items(expected_items: usize) -> Self {
+        CompactTree::with_expected_items(expected_items)
+    }
+
+    fn add_transaction_with_count(&mut self, transaction: Vec<T>, count: usize) {
+        CompactTree::add_transaction_with_count(self, transaction, count)
+    }
+
+    fn node_count(&self) -> usize {
+        CompactTree::node_count(self)
+    }
+
+    fn item_supports(&self) -> Vec<(T, usize)> {
+        CompactTree::item_supports(self)
+    }
+
+    fn partial_tree(&self, item: T, _: Option<&RefCell<NodePool<T>>>) -> Self {
+        self.generate_partial_tree(item)
+    }
+}
+
+/// Resolve the `minimum_support` into the number of transactions of the `source`, which is only
+/// counted for a fraction.
+fn resolve_minimum_support<T: ItemType, S: TransactionSource<T>>(
+    source: &S,
+    minimum_support: Support,
+) -> usize {
+    match minimum_support {
+        Support::Absolute(minimum_support) => minimum_support,
+        Support::Relative(_) => minimum_support.absolute(source.transactions_num()),
+    }
+}
+
+/// Count the maximal patterns of the `fp_result`, the patterns which could not be extended by
+/// any of the `frequent_items` to another frequent pattern.
+fn count_maximal_patterns<T: ItemType>(fp_result: &FPResult<T>, frequent_items: &[T]) -> usize {
+    let patterns: HashSet<&[T]> = fp_result
+        .frequent_patterns
+        .iter()
+        .map(|(pattern, _)| pattern.items())
+        .collect();
+    let mut extended_pattern = vec![];
+    let mut maximal_pattern_count = 0;
+    for pattern in patterns.iter() {
+        let is_maximal = frequent_items.iter().all(|item| {
+            let index = match pattern.binary_search(item) {
+                Ok(_) => return true,
+                Err(index) => index,
+            };
+            extended_pattern.clear();
+            extended_pattern.extend_from_slice(&pattern[..index]);
+            extended_pattern.push(*item);
+            extended_pattern.extend_from_slice(&pattern[index..]);
+            !patterns.contains(extended_pattern.as_slice())
+        });
+        if is_maximal {
+            maximal_pattern_count += 1;
+        }
+    }
+    maximal_pattern_count
+}
+
+/// `PatternSink<T>` receives the frequent patterns and elimination sets found during the mining.
+trait PatternSink<T> {
+    fn frequent_pattern(&mut self, pattern: &[T], support: usize);
+
+    fn elimination_set(&mut self, set: Vec<T>);
+
+    /// Check whether no more pattern should be emitted, so the mining could stop early.
+    fn is_full(&self) -> bool {
+        false
+    }
+
+    /// Return the support below which no more pattern is wanted, it only goes up while mining.
+    fn minimum_support(&self) -> usize {
+        0
+    }
+}
+
+impl<T: ItemType> PatternSink<T> for FPResult<T> {
+    fn frequent_pattern(&mut self, pattern: &[T], support: usize) {
+        self.frequent_patterns
+            .push((Itemset::from(pattern), support));
+    }
+
+    fn elimination_set(&mut self, set: Vec<T>) {
+        self.elimination_sets.insert(set);
+    }
+}
+
+/// `ChannelSink<T>` sends the frequent patterns through a channel and ignores the elimination sets.
+struct ChannelSink<T>(Sender<(Vec<T>, usize)>);
+
+impl<T: ItemType> PatternSink<T> for ChannelSink<T> {
+    fn frequent_pattern(&mut self, pattern: &[T], support: usize) {
+        // The receiver may hang up early, there is nothing else to do in that case.
+        let _ = self.0.send((pattern.to_vec(), support));
+    }
+
+    fn elimination_set(&mut self, _: Vec<T>) {}
+}
+
+/// `NewPatternSink<T>` passes the frequent patterns whose support is lower than `known_support`
+/// to the inner result, the ones with a higher support are known already.
+struct NewPatternSink<'a, T> {
+    fp_result: &'a mut FPResult<T>,
+    known_support: usize,
+}
+
+impl<T: ItemType> PatternSink<T> for NewPatternSink<'_, T> {
+    fn frequent_pattern(&mut self, pattern: &[T], support: usize) {
+        if support < self.known_support {
+            self.fp_result.frequent_pattern(pattern, support);
+        }
+    }
+
+    fn elimination_set(&mut self, set: Vec<T>) {
+        self.fp_result.elimination_set(set);
+    }
+}
+
+/// `ObserverSink<T>` passes the frequent patterns to the inner result and the elimination sets
+/// to the observer.
+struct ObserverSink<'a, T> {
+    fp_result: &'a mut FPResult<T>,
+    observer: &'a mut dyn FnMut(&[T]),
+}
+
+impl<T: ItemType> PatternSink<T> for ObserverSink<'_, T> {
+    fn frequent_pattern(&mut self, pattern: &[T], support: usize) {
+        self.fp_result.frequent_pattern(pattern, support);
+    }
+
+    fn elimination_set(&mut self, set: Vec<T>) {
+        (self.observer)(&set);
+    }
+}
+
+/// `MaximalPatterns<T>` holds the maximal patterns found so far, every item is indexed to the
+/// patterns holding it, so only the patterns holding the items of a pattern are checked to
+/// tell whether it's subsumed.
+struct MaximalPatterns<T> {
+    patterns: Vec<(Vec<T>, usize)>,
+    item_patterns: HashMap<T, Vec<usize>>,
+}
+
+impl<T: ItemType> MaximalPatterns<T> {
+    /// Check whether the non-empty sorted `pattern` is a subset of any maximal pattern.
+    fn subsumes(&self, pattern: &[T]) -> bool {
+        let indexes = pattern
+            .iter()
+            .map(|item| self.item_patterns.get(item))
+            .min_by_key(|indexes| indexes.map_or(0, |indexes| indexes.len()));
+        match indexes {
+            Some(Some(indexes)) => indexes
+                .iter()
+                .any(|&index| is_subset(pattern, &self.patterns[index].0)),
+            _ => false,
+        }
+    }
+
+    fn insert(&mut self, pattern: Vec<T>, support: usize) {
+        for &item in pattern.iter() {
+            self.item_patterns
+                .entry(item)
+                .or_default()
+                .push(self.patterns.len());
+        }
+        self.patterns.push((pattern, support));
+    }
+}
+
+/// `ClosedSink<T>` keeps only the closed patterns among the ones emitted so far, grouped by the
+/// support, since only a superset with the same support could subsume a pattern.
+struct ClosedSink<T> {
+    support_groups: HashMap<usize, Vec<Vec<T>>>,
+}
+
+impl<T: ItemType> PatternSink<T> for ClosedSink<T> {
+    fn frequent_pattern(&mut self, pattern: &[T], support: usize) {
+        let mut pattern = pattern.to_vec();
+        pattern.sort();
+        let patterns = self.support_groups.entry(support).or_default();
+        if patterns
+            .iter()
+            .any(|closed_pattern| is_subset(&pattern, closed_pattern))
+        {
+            return;
+        }
+        // The supersets are not always mined before their subsets.
+        patterns.retain(|closed_pattern| !is_subset(closed_pattern, &pattern));
+        patterns.push(pattern);
+    }
+
+    fn elimination_set(&mut self, _: Vec<T>) {}
+}
+
+/// `CappedSink<T>` passes at most `max_results` frequent patterns to the inner sink, and becomes
+/// full once another pattern is found beyond that.
+struct CappedSink<'a, T> {
+    sink: &'a mut dyn PatternSink<T>,
+    max_results: usize,
+    results_num: usize,
+    is_capped: bool,
+}
+
+impl<T: ItemType> PatternSink<T> for CappedSink<'_, T> {
+    fn frequent_pattern(&mut self, pattern: &[T], support: usize) {
+        if self.results_num < self.max_results {
+            self.sink.frequent_pattern(pattern, support);
+            self.results_num += 1;
+        } else {
+            self.is_capped = true;
+        }
+    }
+
+    fn elimination_set(&mut self, set: Vec<T>) {
+        self.sink.elimination_set(set);
+    }
+
+    fn is_full(&self) -> bool {
+        self.is_capped || self.sink.is_full()
+    }
+
+    fn minimum_support(&self) -> usize {
+        self.sink.minimum_support()
+    }
+}
+
+/// `TopKSink<T>` keeps the `k` best frequent patterns by the support, the ties broken
+/// lexicographically, in a heap whose top is the worst one of them.
+struct TopKSink<T> {
+    k: usize,
+    patterns: BinaryHeap<(Reverse<usize>, Vec<T>)>,
+}
+
+impl<T: ItemType> PatternSink<T> for TopKSink<T> {
+    fn frequent_pattern(&mut self, pattern: &[T], support: usize) {
+        let mut pattern = pattern.to_vec();
+        pattern.sort();
+        let entry = (Reverse(support), pattern);
+        if self.patterns.len() < self.k {
+            self.patterns.push(entry);
+        } else if self.patterns.peek().is_some_and(|worst| entry < *worst) {
+            self.patterns.pop();
+            self.patterns.push(entry);
+        }
+    }
+
+    fn elimination_set(&mut self, _: Vec<T>) {}
+
+    fn minimum_support(&self) -> usize {
+        match self.patterns.len() < self.k {
+            true => 0,
+            false => self
+                .patterns
+                .peek()
+                .map_or(0, |(Reverse(support), _)| *support),
+        }
+    }
 }
@@ -0,0 +1,128 @@
+//! `closure` implements the closed patterns of a FP-Growth result, a frequent pattern is closed
+//! if none of its supersets has the same support.
+
+use std::collections::HashMap;
+
+use crate::algorithm::FPResult;
+use crate::ItemType;
+
+/// `ClosureFamily<T>` groups a closed pattern with all its sub-patterns sharing the same support,
+/// i.e. the frequent patterns whose closure is the closed pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClosureFamily<T> {
+    closed_pattern: Vec<T>,
+    support: usize,
+    sub_patterns: Vec<Vec<T>>,
+}
+
+impl<T: ItemType> ClosureFamily<T> {
+    /// Return the closed pattern with its items sorted.
+    pub fn closed_pattern(&self) -> &[T] {
+        &self.closed_pattern
+    }
+
+    pub fn support(&self) -> usize {
+        self.support
+    }
+
+    /// Return the proper sub-patterns with their items sorted, the closed pattern is not included.
+    pub fn sub_patterns(&self) -> &[Vec<T>] {
+        &self.sub_patterns
+    }
+}
+
+impl<T: ItemType> FPResult<T> {
+    /// Return the closed patterns with their items sorted, ordered by the support in
+    /// descending order and then lexicographically.
+    pub fn closed_patterns(&self) -> Vec<(Vec<T>, usize)> {
+        self.closure_families()
+            .into_iter()
+            .map(|family| (family.closed_pattern, family.support))
+            .collect()
+    }
+
+    /// Return the patterns closed within the tolerance `epsilon`, i.e. the frequent patterns
+    /// none of whose supersets has a support lower by at most `epsilon`, ordered like
+    /// [`FPResult::closed_patterns()`]. The patterns are the same as the closed ones when
+    /// `epsilon` is 0, and a larger `epsilon` merges the patterns of nearly equal supports.
+    pub fn closed_patterns_within(&self, epsilon: usize) -> Vec<(Vec<T>, usize)> {
+        let frequent_items: Vec<T> = self
+            .frequent_patterns
+            .iter()
+            .filter(|(pattern, _)| pattern.len() == 1)
+            .map(|(pattern, _)| pattern[0])
+            .collect();
+        let mut closed_patterns: Vec<(Vec<T>, usize)> = self
+            .frequent_patterns
+            .iter()
+            .filter(|(pattern, support)| {
+                // A superset within the tolerance implies such a superset with one more item,
+                // since the supports in between are within the tolerance as well.
+                let mut superset = pattern.to_vec();
+                superset.push(pattern[0]);
+                !frequent_items
+                    .iter()
+                    .filter(|item| !pattern.contains(item))
+                    .any(|&item| {
+                        *superset.last_mut().unwrap() = item;
+                        self.support_of(&superset).is_some_and(|superset_support| {
+                            superset_support.saturating_add(epsilon) >= *support
+                        })
+                    })
+            })
+            .map(|(pattern, support)| (pattern.to_vec(), *support))
+            .collect();
+        closed_patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        closed_patterns
+    }
+
+    /// Group the frequent patterns by their closure, every family holds a closed pattern and
+    /// all its sub-patterns with the same support. The families are ordered by the support in
+    /// descending order and then lexicographically by the closed pattern.
+    pub fn closure_families(&self) -> Vec<ClosureFamily<T>> {
+        // Only the patterns with the same support could be in the same family.
+        let mut support_groups: HashMap<usize, Vec<Vec<T>>> = HashMap::new();
+        for (pattern, support) in self.frequent_patterns.iter() {
+            support_groups
+                .entry(*support)
+                .or_default()
+                .push(pattern.to_vec());
+        }
+
+        let mut families = vec![];
+        for (support, mut patterns) in support_groups.into_iter() {
+            // Visit the longer patterns first, so the closure of a pattern is always
+            // visited before the pattern itself.
+            patterns.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+            let mut group_families: Vec<ClosureFamily<T>> = vec![];
+            for pattern in patterns.into_iter() {
+                match group_families
+                    .iter_mut()
+                    .find(|family| is_subset(&pattern, &family.closed_pattern))
+                {
+                    Some(family) => family.sub_patterns.push(pattern),
+                    None => group_families.push(ClosureFamily {
+                        closed_pattern: pattern,
+                        support,
+                        sub_patterns: vec![],
+                    }),
+                }
+            }
+            families.append(&mut group_families);
+        }
+        families.sort_by(|a, b| {
+            b.support
+                .cmp(&a.support)
+                .then_with(|| a.closed_pattern.cmp(&b.closed_pattern))
+        });
+        families
+    }
+}
+
+/// Check whether the sorted `small` is a subset of the sorted `large`.
+pub(crate) fn is_subset<T: ItemType>(small: &[T], large: &[T]) -> bool {
+    let mut large_iter = large.iter();
+    small
+        .iter()
+        .all(|item| large_iter.any(|large_item| large_item == item))
+}
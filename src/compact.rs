@@ -0,0 +1,176 @@
+//! `compact` implements a FP-Growth tree stored in the columns of dense arrays, which takes much
+//! less memory per node than the `Rc<Node<T>>` graph of [`crate::tree::Tree<T>`].
+
+use std::{collections::HashMap, convert::TryFrom, iter, mem::size_of};
+
+use crate::ItemType;
+
+/// The index of no node, e.g. the parent of the root's children.
+const NONE: u32 = u32::MAX;
+
+/// `CompactTree<T>` represents a FP-Growth tree whose nodes are the indexes into the columns
+/// holding their items, counts and links, e.g. a tree of `u64` item ids takes 32 bytes per
+/// node rather than an `Rc<Node<u64>>` with its children list and weak links. The root node is
+/// implicit, and a tree could hold at most `u32::MAX` nodes.
+///
+/// Mine with it by [`crate::algorithm::FPGrowth::with_compact_tree()`].
+#[derive(Clone, Debug)]
+pub struct CompactTree<T> {
+    items: Vec<T>,
+    counts: Vec<usize>,
+    parents: Vec<u32>,
+    first_children: Vec<u32>,
+    next_siblings: Vec<u32>,
+    // neighbors links every node to the next node holding the same item.
+    neighbors: Vec<u32>,
+    // root_first_child is the first child of the implicit root node.
+    root_first_child: u32,
+    // routes maps every item to the first and the last node holding it.
+    routes: HashMap<T, (u32, u32)>,
+}
+
+impl<T: ItemType> Default for CompactTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ItemType> CompactTree<T> {
+    /// Create a new empty tree.
+    pub fn new() -> CompactTree<T> {
+        CompactTree::with_expected_items(0)
+    }
+
+    /// Create a new empty tree which is expected to hold `expected_items` distinct items,
+    /// the route table is reserved in advance.
+    pub fn with_expected_items(expected_items: usize) -> CompactTree<T> {
+        CompactTree {
+            items: vec![],
+            counts: vec![],
+            parents: vec![],
+            first_children: vec![],
+            next_siblings: vec![],
+            neighbors: vec![],
+            root_first_child: NONE,
+            routes: HashMap::with_capacity(expected_items),
+        }
+    }
+
+    /// Return the number of nodes in this tree, the implicit root node is not included.
+    pub fn node_count(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Add the items of the transaction to this tree as a path from the root.
+    pub fn add_transaction(&mut self, transaction: Vec<T>) {
+        self.add_path(transaction.into_iter(), 1);
+    }
+
+    /// Add the transaction occurring `count` times to this tree at once, which is the same as
+    /// adding it `count` times.
+    pub fn add_transaction_with_count(&mut self, transaction: Vec<T>, count: usize) {
+        self.add_path(transaction.into_iter(), count);
+    }
+
+    /// Return the number of transactions in this tree which contain the `item`.
+    pub fn item_support(&self, item: T) -> usize {
+        self.item_nodes(item)
+            .map(|node| self.counts[node as usize])
+            .sum()
+    }
+
+    /// Return every item in this tree with its support, in no particular order.
+    pub fn item_supports(&self) -> Vec<(T, usize)> {
+        self.routes
+            .keys()
+            .map(|&item| (item, self.item_support(item)))
+            .collect()
+    }
+
+    /// Generate the partial tree of the prefix paths ending with the given item, like
+    /// [`crate::tree::Tree::generate_partial_tree()`] does with the paths generated by
+    /// [`crate::tree::Tree::generate_prefix_path()`].
+    pub fn generate_partial_tree(&self, item: T) -> CompactTree<T> {
+        let mut partial_tree = CompactTree::new();
+        let mut path = vec![];
+        for end_node in self.item_nodes(item) {
+            path.clear();
+            let mut cur_node = end_node;
+            while cur_node != NONE {
+                path.push(self.items[cur_node as usize]);
+                cur_node = self.parents[cur_node as usize];
+            }
+            partial_tree.add_path(path.iter().rev().copied(), self.counts[end_node as usize]);
+        }
+        partial_tree
+    }
+
+    /// Return the number of bytes allocated by this tree, which is proportional to the
+    /// number of nodes besides the route table.
+    pub fn estimated_bytes(&self) -> usize {
+        self.items.capacity() * size_of::<T>()
+            + self.counts.capacity() * size_of::<usize>()
+            + (self.parents.capacity()
+                + self.first_children.capacity()
+                + self.next_siblings.capacity()
+                + self.neighbors.capacity())
+                * size_of::<u32>()
+            + self.routes.capacity() * size_of::<(T, (u32, u32))>()
+    }
+
+    /// Return an iterator over the nodes holding the `item`.
+    fn item_nodes(&self, item: T) -> impl Iterator<Item = u32> + '_ {
+        let head_node = self.routes.get(&item).map(|&(head_node, _)| head_node);
+        iter::successors(head_node, move |&node| {
+            Some(self.neighbors[node as usize]).filter(|&neighbor| neighbor != NONE)
+        })
+    }
+
+    /// Add the `items` as a path from the root, and `count` to every node of the path.
+    fn add_path(&mut self, items: impl Iterator<Item = T>, count: usize) {
+        let mut parent_node = NONE;
+        for item in items {
+            let mut child_node = match parent_node {
+                NONE => self.root_first_child,
+                _ => self.first_children[parent_node as usize],
+            };
+            while child_node != NONE && self.items[child_node as usize] != item {
+                child_node = self.next_siblings[child_node as usize];
+            }
+            match child_node {
+                NONE => child_node = self.push_node(item, count, parent_node),
+                _ => self.counts[child_node as usize] += count,
+            }
+            parent_node = child_node;
+        }
+    }
+
+    /// Push a new node as the first child of the `parent_node`, and link it to the route.
+    fn push_node(&mut self, item: T, count: usize, parent_node: u32) -> u32 {
+        let node = match u32::try_from(self.items.len()) {
+            Ok(node) if node != NONE => node,
+            _ => panic!("a compact tree could hold at most {} nodes", NONE),
+        };
+        let first_child = match parent_node {
+            NONE => &mut self.root_first_child,
+            _ => &mut self.first_children[parent_node as usize],
+        };
+        self.next_siblings.push(*first_child);
+        *first_child = node;
+        self.items.push(item);
+        self.counts.push(count);
+        self.parents.push(parent_node);
+        self.first_children.push(NONE);
+        self.neighbors.push(NONE);
+        match self.routes.get_mut(&item) {
+            Some((_, tail_node)) => {
+                self.neighbors[*tail_node as usize] = node;
+                *tail_node = node;
+            }
+            None => {
+                self.routes.insert(item, (node, node));
+            }
+        }
+        node
+    }
+}
@@ -0,0 +1,57 @@
+//! `error` defines the errors that could be returned during the FP-Growth algorithm.
+
+use std::fmt;
+
+/// `Error` represents the reasons why a FP-Growth algorithm run could fail.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The FP-Growth tree needs more nodes than the budget set by
+    /// [`crate::algorithm::FPGrowth::with_memory_budget()`].
+    MemoryBudgetExceeded { max_nodes: usize },
+    /// The minimum support is larger than the number of transactions, so no pattern could be frequent.
+    SupportExceedsTransactions {
+        minimum_support: usize,
+        transactions_num: usize,
+    },
+    /// The item is not lower than the universe size of the
+    /// [`crate::source::BitsetTransactions`].
+    ItemOutOfUniverse { item: u32, universe_size: usize },
+    /// The item is an ancestor of itself in the hierarchy set by
+    /// [`crate::algorithm::FPGrowth::with_item_hierarchy()`], it's formatted by `Debug`.
+    CyclicHierarchy { item: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MemoryBudgetExceeded { max_nodes } => {
+                write!(
+                    f,
+                    "the tree exceeds the memory budget of {} nodes",
+                    max_nodes
+                )
+            }
+            Error::SupportExceedsTransactions {
+                minimum_support,
+                transactions_num,
+            } => write!(
+                f,
+                "the minimum support {} exceeds the number of transactions {}",
+                minimum_support, transactions_num
+            ),
+            Error::ItemOutOfUniverse {
+                item,
+                universe_size,
+            } => write!(
+                f,
+                "the item {} is out of the universe of {} items",
+                item, universe_size
+            ),
+            Error::CyclicHierarchy { item } => {
+                write!(f, "the item {} is an ancestor of itself", item)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,62 @@
+//! `itemset` implements the set of items a frequent pattern holds.
+
+use std::{borrow::Borrow, ops::Deref};
+
+use crate::ItemType;
+
+/// `Itemset<T>` wraps the items of a pattern sorted and deduplicated, so two itemsets holding
+/// the same items are always equal, hashed and ordered the same way no matter how the items
+/// were ordered when they were created.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Itemset<T> {
+    items: Vec<T>,
+}
+
+impl<T: ItemType> Itemset<T> {
+    /// Return the items sorted in ascending order.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+
+    /// Check whether the given `item` is in this itemset.
+    pub fn contains(&self, item: &T) -> bool {
+        self.items.binary_search(item).is_ok()
+    }
+
+    /// Check whether all items of this itemset are in the `other` one.
+    pub fn is_subset(&self, other: &Itemset<T>) -> bool {
+        self.items.iter().all(|item| other.contains(item))
+    }
+}
+
+impl<T: ItemType> From<Vec<T>> for Itemset<T> {
+    fn from(mut items: Vec<T>) -> Self {
+        items.sort();
+        items.dedup();
+        Itemset { items }
+    }
+}
+
+impl<T: ItemType> From<&[T]> for Itemset<T> {
+    fn from(items: &[T]) -> Self {
+        Itemset::from(items.to_vec())
+    }
+}
+
+impl<T> Deref for Itemset<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T> Borrow<[T]> for Itemset<T> {
+    fn borrow(&self) -> &[T] {
+        &self.items
+    }
+}
@@ -0,0 +1,57 @@
+//! `lattice` renders the lattice of the frequent patterns, where every pattern is linked to its
+//! immediate supersets.
+
+use std::{collections::HashMap, fmt::Write};
+
+use crate::algorithm::FPResult;
+use crate::itemset::Itemset;
+use crate::ItemType;
+
+impl<T: ItemType> FPResult<T> {
+    /// Render the lattice of the frequent patterns in the Graphviz DOT language. Every node is
+    /// labeled with a pattern and its support, and every edge links a pattern to an immediate
+    /// superset, i.e. one with one more item. The more support a pattern has, the deeper its
+    /// node is filled.
+    ///
+    /// The output is deterministic, the patterns are ordered by their length and then their items.
+    pub fn lattice_to_dot(&self) -> String {
+        let mut patterns: Vec<&(Itemset<T>, usize)> = self.frequent_patterns.iter().collect();
+        patterns.sort_by(|a, b| a.0.len().cmp(&b.0.len()).then_with(|| a.0.cmp(&b.0)));
+        let node_ids: HashMap<&[T], usize> = patterns
+            .iter()
+            .enumerate()
+            .map(|(node_id, (pattern, _))| (pattern.items(), node_id))
+            .collect();
+        let max_support = patterns
+            .iter()
+            .map(|(_, support)| *support)
+            .max()
+            .unwrap_or(0);
+
+        let mut dot = String::from("digraph lattice {\n    rankdir=BT;\n");
+        for (node_id, (pattern, support)) in patterns.iter().enumerate() {
+            let items: Vec<String> = pattern.iter().map(|item| format!("{:?}", item)).collect();
+            let label = format!("{{{}}}\\n{}", items.join(", "), support);
+            // The saturation of the fill color grows with the support.
+            let saturation = *support as f64 / max_support as f64;
+            let _ = writeln!(
+                dot,
+                "    {} [label=\"{}\", style=filled, fillcolor=\"0.6 {:.3} 1.0\"];",
+                node_id,
+                label.replace('"', "\\\""),
+                saturation
+            );
+        }
+        for (node_id, (pattern, _)) in patterns.iter().enumerate() {
+            for index in 0..pattern.len() {
+                let mut subset = pattern.to_vec();
+                subset.remove(index);
+                if let Some(subset_id) = node_ids.get(subset.as_slice()) {
+                    let _ = writeln!(dot, "    {} -> {};", subset_id, node_id);
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
@@ -3,10 +3,11 @@
 //! # Usage
 //!
 //! To get you started quickly, the easiest and highest-level way to get
-//! a FP-Growth algorithm result is to use [`algorithm::FPGrowth::find_frequent_patterns()`]
+//! a FP-Growth algorithm result is to use [`algorithm::FPGrowth::find_frequent_patterns()`].
+//! All the commonly used types could be imported at once from the [`prelude`].
 //!
 //! ```
-//! use fp_growth::algorithm::*;
+//! use fp_growth::prelude::*;
 //!
 //! let transactions = vec![
 //!     vec!["e", "c", "a", "b", "f", "h"],
@@ -33,6 +34,19 @@
 use std::{fmt::Debug, hash::Hash};
 
 pub mod algorithm;
+pub mod closure;
+pub mod compact;
+pub mod error;
+pub mod itemset;
+pub mod lattice;
+pub mod measure;
+pub mod prelude;
+pub mod rule;
+pub mod sequence;
+pub mod source;
+mod sync;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod tree;
 
 pub trait ItemType: Eq + Ord + Hash + Copy + Debug {}
@@ -41,9 +55,86 @@ impl<T> ItemType for T where T: Eq + Ord + Hash + Copy + Debug {}
 
 #[cfg(test)]
 mod tests {
-    use crate::algorithm::FPGrowth;
-    use crate::tree::{Node, Tree};
-    use std::rc::Rc;
+    use crate::algorithm::{FPGrowth, FPResult, SortKey, Support};
+    use crate::compact::CompactTree;
+    use crate::error::Error;
+    use crate::itemset::Itemset;
+    use crate::measure::{Combine, InterestMeasure};
+    use crate::rule::AssociationRule;
+    use crate::sequence::PrefixSpan;
+    use crate::source::{BitsetTransactions, Transaction, TransactionSource};
+    use crate::sync::Rc;
+    use crate::testing::{
+        generate_synthetic_transactions, planted_itemset, random_transactions, SplitMix64,
+    };
+    use crate::tree::{FrozenTree, Node, NodePool, Tree};
+    use crate::ItemType;
+    use std::borrow::Cow;
+    use std::collections::{BTreeSet, HashMap, HashSet};
+    use std::fs;
+    use std::mem::size_of;
+    use std::sync::mpsc::channel;
+    use std::sync::Arc;
+    use std::thread;
+
+    /// Sort the items of every frequent pattern and then the patterns themselves,
+    /// so that two results could be compared.
+    fn sorted_patterns<T: ItemType>(result: &FPResult<T>) -> Vec<(Vec<T>, usize)> {
+        let mut patterns: Vec<(Vec<T>, usize)> = result
+            .frequent_patterns()
+            .into_iter()
+            .map(|(mut pattern, support)| {
+                pattern.sort();
+                (pattern, support)
+            })
+            .collect();
+        patterns.sort();
+        patterns
+    }
+
+    /// The transactions mined by most of the algorithm tests, some of them repeat an item.
+    fn fixture_transactions() -> Vec<Vec<&'static str>> {
+        vec![
+            vec!["a", "c", "e", "b", "f", "h", "a", "e", "f"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["e", "c", "a", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e", "e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+            vec!["g", "c", "e", "a"],
+            vec!["a", "c", "e", "g"],
+            vec!["i"],
+        ]
+    }
+
+    /// The transactions built into a tree by the tree tests.
+    fn tree_fixture_transactions() -> Vec<Vec<&'static str>> {
+        vec![
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["a", "c", "e", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["a", "c", "e", "g"],
+        ]
+    }
+
+    /// A handful of transactions small enough to work out the supports and the rules by hand.
+    fn small_fixture_transactions() -> Vec<Vec<&'static str>> {
+        vec![
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "b"],
+            vec!["a", "d"],
+            vec!["c", "d"],
+        ]
+    }
 
     #[test]
     fn test_node() {
@@ -70,41 +161,97 @@ mod tests {
         assert_eq!(child_node_2.item(), Some(2));
     }
 
+    #[test]
+    fn test_is_ancestor_of() {
+        let root_node = Node::<i32>::new_rc(None, 0);
+        let child_node_1 = Rc::new(Node::<i32>::new(Some(1), 1));
+        let child_node_2 = Rc::new(Node::<i32>::new(Some(2), 2));
+        root_node.add_child(Rc::clone(&child_node_1));
+        child_node_1.add_child(Rc::clone(&child_node_2));
+
+        assert!(root_node.is_ancestor_of(&child_node_2));
+        assert!(child_node_1.is_ancestor_of(&child_node_2));
+        assert!(!child_node_2.is_ancestor_of(&child_node_1));
+        assert!(!child_node_1.is_ancestor_of(&child_node_1));
+        // A node with the same item under the same parent is not the same node.
+        let twin_node_1 = Node::<i32>::new_rc(Some(1), 1);
+        root_node.add_child(Rc::clone(&twin_node_1));
+        assert!(*twin_node_1 == *child_node_1);
+        assert!(!twin_node_1.is_ancestor_of(&child_node_2));
+    }
+
+    #[test]
+    fn test_subtree_support() {
+        let root_node = Node::<i32>::new_rc(None, 0);
+        let child_node_1 = Rc::new(Node::<i32>::new(Some(1), 1));
+        let child_node_2 = Rc::new(Node::<i32>::new(Some(2), 2));
+        root_node.add_child(Rc::clone(&child_node_1));
+        child_node_1.add_child(Rc::clone(&child_node_2));
+        child_node_1.add_child(Rc::new(Node::<i32>::new(Some(3), 4)));
+
+        assert_eq!(root_node.subtree_support(), 7);
+        assert_eq!(child_node_1.subtree_support(), 7);
+        assert_eq!(child_node_2.subtree_support(), 2);
+    }
+
     #[test]
     fn test_tree() {
         let mut tree = Tree::<&str>::new();
-        let transactions = vec![
-            vec!["a", "c", "e", "b", "f"],
-            vec!["a", "c", "g"],
-            vec!["e"],
-            vec!["a", "c", "e", "g", "d"],
-            vec!["a", "c", "e", "g"],
-            vec!["e"],
-            vec!["a", "c", "e", "b", "f"],
-            vec!["a", "c", "d"],
-            vec!["a", "c", "e", "g"],
-            vec!["a", "c", "e", "g"],
-        ];
+        let transactions = tree_fixture_transactions();
         for transaction in transactions.into_iter() {
             tree.add_transaction(transaction);
         }
     }
 
+    #[test]
+    fn test_header_summary() {
+        let mut tree = Tree::<&str>::new();
+        for transaction in tree_fixture_transactions().into_iter() {
+            tree.add_transaction(transaction);
+        }
+
+        let header_rows = tree.header_summary();
+        assert_eq!(header_rows.len(), 7);
+        assert_eq!(
+            (
+                header_rows[0].item(),
+                header_rows[0].support(),
+                header_rows[0].node_count()
+            ),
+            ("a", 8, 1)
+        );
+        assert_eq!(
+            (
+                header_rows[2].item(),
+                header_rows[2].support(),
+                header_rows[2].node_count()
+            ),
+            ("e", 8, 2)
+        );
+        assert!(header_rows
+            .windows(2)
+            .all(|rows| rows[0].support() >= rows[1].support()));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut tree = Tree::<&str>::new();
+        for transaction in tree_fixture_transactions().into_iter() {
+            tree.add_transaction(transaction);
+        }
+
+        let nodes = tree.get_all_nodes("e");
+        assert_eq!(nodes.len(), 2);
+        let neighbors: Vec<Rc<Node<&str>>> = nodes[0].neighbors().collect();
+        assert_eq!(neighbors, nodes[1..].to_vec());
+        assert_eq!(nodes[1].neighbors().count(), 0);
+        assert_eq!(tree.get_all_nodes("g").len(), 2);
+        assert_eq!(tree.get_all_nodes("g")[0].neighbors().count(), 1);
+    }
+
     #[test]
     fn test_algorithm() {
-        let transactions = vec![
-            vec!["a", "c", "e", "b", "f", "h", "a", "e", "f"],
-            vec!["a", "c", "g"],
-            vec!["e"],
-            vec!["e", "c", "a", "g", "d"],
-            vec!["a", "c", "e", "g"],
-            vec!["e", "e"],
-            vec!["a", "c", "e", "b", "f"],
-            vec!["a", "c", "d"],
-            vec!["g", "c", "e", "a"],
-            vec!["a", "c", "e", "g"],
-            vec!["i"],
-        ];
+        let transactions = fixture_transactions();
         // FIXME: use specific result cases to verify correctness.
         let test_cases: Vec<(usize, usize, usize)> = vec![
             // (minimum_support, frequent_patterns_num, elimination_set_num)
@@ -125,4 +272,2967 @@ mod tests {
             assert_eq!(*elimination_set_num, result.elimination_sets_num());
         }
     }
+
+    #[test]
+    fn test_pattern_count_curve() {
+        let fp_growth_str = FPGrowth::<&str>::new(fixture_transactions(), 1);
+        // The same numbers of patterns as mining at every threshold in test_algorithm.
+        assert_eq!(
+            fp_growth_str.pattern_count_curve(&[1, 2, 3, 4, 5, 6, 7, 8, 9]),
+            vec![
+                (1, 88),
+                (2, 43),
+                (3, 15),
+                (4, 15),
+                (5, 11),
+                (6, 7),
+                (7, 4),
+                (8, 4),
+                (9, 0)
+            ]
+        );
+        assert!(fp_growth_str.pattern_count_curve(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_distinct_item_count() {
+        // The infrequent items are counted whatever the minimum support is.
+        for minimum_support in [1, 2, 9].iter().copied() {
+            let fp_growth_str = FPGrowth::<&str>::new(fixture_transactions(), minimum_support);
+            assert_eq!(fp_growth_str.distinct_item_count(), 9);
+        }
+        let fp_growth_str = FPGrowth::<&str>::new(small_fixture_transactions(), 3);
+        assert_eq!(fp_growth_str.distinct_item_count(), 4);
+    }
+
+    #[test]
+    fn test_memory_budget() {
+        let transactions = tree_fixture_transactions();
+        let unlimited = FPGrowth::<&str>::new(transactions.clone(), 2).find_frequent_patterns();
+        let fp_growth_str = FPGrowth::<&str>::new(transactions.clone(), 2).with_memory_budget(100);
+        let result = fp_growth_str.try_find_frequent_patterns().unwrap();
+        assert_eq!(
+            unlimited.frequent_patterns_num(),
+            result.frequent_patterns_num()
+        );
+
+        let fp_growth_str = FPGrowth::<&str>::new(transactions, 2).with_memory_budget(4);
+        assert_eq!(
+            fp_growth_str.try_find_frequent_patterns().unwrap_err(),
+            Error::MemoryBudgetExceeded { max_nodes: 4 }
+        );
+        // The minimum support is raised until the tree only keeps "a", "c" and "e".
+        let result = fp_growth_str.find_frequent_patterns();
+        assert!(result.frequent_patterns_num() > 0);
+        assert!(result.frequent_patterns_num() < unlimited.frequent_patterns_num());
+    }
+
+    #[test]
+    fn test_mine_existing_tree() {
+        let transactions = tree_fixture_transactions();
+        let mut tree = Tree::<&str>::new();
+        for transaction in transactions.clone().into_iter() {
+            tree.add_transaction(transaction);
+        }
+        for minimum_support in 1..=9 {
+            let result = FPGrowth::mine_existing_tree(&tree, minimum_support);
+            let expected = FPGrowth::<&str>::new(transactions.clone(), minimum_support)
+                .find_frequent_patterns();
+            assert_eq!(
+                expected.frequent_patterns_num(),
+                result.frequent_patterns_num()
+            );
+        }
+    }
+
+    #[test]
+    fn test_estimated_bytes() {
+        let result = FPResult::new(vec![(vec![1u32, 2], 3), (vec![1], 4)], HashSet::new());
+        let entry_size = size_of::<(Vec<u32>, usize)>();
+        assert_eq!(
+            result.estimated_bytes(),
+            3 * size_of::<u32>() + 2 * entry_size
+        );
+
+        let mut elimination_sets = HashSet::new();
+        elimination_sets.insert(vec![5u32, 6, 7]);
+        let result = FPResult::new(vec![], elimination_sets);
+        assert_eq!(
+            result.estimated_bytes(),
+            3 * size_of::<u32>() + size_of::<Vec<u32>>()
+        );
+    }
+
+    #[test]
+    fn test_patterns_matching() {
+        let result = FPResult::new(
+            vec![
+                (vec!["a"], 5),
+                (vec!["b", "a"], 4),
+                (vec!["c", "a"], 3),
+                (vec!["d", "a"], 3),
+                (vec!["c", "b"], 2),
+            ],
+            HashSet::new(),
+        );
+        let mut matched = result.patterns_matching(&["a"], &["b", "c"]);
+        matched.sort();
+        assert_eq!(matched, vec![(vec!["a", "b"], 4), (vec!["a", "c"], 3)]);
+        assert_eq!(result.patterns_matching(&["a"], &[]).len(), 4);
+        assert_eq!(result.patterns_matching(&[], &["c"]).len(), 2);
+        assert!(result.patterns_matching(&["e"], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_generators() {
+        let transactions = small_fixture_transactions();
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        let mut generators: Vec<(Vec<&str>, usize)> = result
+            .generators()
+            .into_iter()
+            .map(|(mut pattern, support)| {
+                pattern.sort();
+                (pattern, support)
+            })
+            .collect();
+        generators.sort();
+        // ["a", "b"] shares the same support with ["b"] and ["a", "b", "c"] shares the same
+        // support with ["a", "c"], so neither of them is a generator.
+        assert_eq!(
+            generators,
+            vec![
+                (vec!["a"], 4),
+                (vec!["a", "c"], 2),
+                (vec!["b"], 3),
+                (vec!["b", "c"], 2),
+                (vec!["c"], 3),
+                (vec!["d"], 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mine_to_channel() {
+        let transactions = vec![
+            vec![1, 3, 5, 2, 6],
+            vec![1, 3, 7],
+            vec![5],
+            vec![1, 3, 5, 7, 4],
+            vec![1, 3, 5, 7],
+            vec![1, 3, 4],
+        ];
+        let fp_growth = FPGrowth::<i32>::new(transactions, 2);
+        let expected = fp_growth.find_frequent_patterns();
+
+        let (tx, rx) = channel();
+        let consumer = thread::spawn(move || rx.iter().count());
+        fp_growth.mine_to_channel(tx);
+        assert_eq!(consumer.join().unwrap(), expected.frequent_patterns_num());
+    }
+
+    #[test]
+    fn test_wide_transactions() {
+        let wide_transaction: Vec<u32> = (0..5000).collect();
+        let mut tree = Tree::<u32>::with_expected_items(5000);
+        tree.add_transaction(wide_transaction.clone());
+        tree.add_transaction(wide_transaction.iter().rev().copied().collect());
+        assert_eq!(tree.node_count(), 10000);
+        assert_eq!(tree.get_all_items_nodes().len(), 5000);
+        assert_eq!(tree.get_all_nodes(4999).len(), 2);
+    }
+
+    #[test]
+    fn test_into_rules() {
+        let transactions = small_fixture_transactions();
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        let rules = result.into_rules(0.7);
+        let mut rule_strs: Vec<String> = rules
+            .iter()
+            .map(|rule| {
+                format!(
+                    "{:?} => {:?} {} {:.2}",
+                    rule.antecedent(),
+                    rule.consequent(),
+                    rule.support(),
+                    rule.confidence()
+                )
+            })
+            .collect();
+        rule_strs.sort();
+        assert_eq!(
+            rule_strs,
+            vec![
+                "[\"a\", \"c\"] => [\"b\"] 2 1.00",
+                "[\"a\"] => [\"b\"] 3 0.75",
+                "[\"b\", \"c\"] => [\"a\"] 2 1.00",
+                "[\"b\"] => [\"a\"] 3 1.00",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_item_priority() {
+        let transactions = vec![
+            vec!["e", "c", "a", "b", "f", "h"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["e", "c", "a", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+            vec!["g", "c", "e", "a"],
+            vec!["a", "c", "e", "g"],
+            vec!["i"],
+        ];
+        let item_priority: HashMap<&str, i64> =
+            vec![("g", 3), ("d", 2), ("f", 1)].into_iter().collect();
+        for minimum_support in 1..=5 {
+            let expected = FPGrowth::<&str>::new(transactions.clone(), minimum_support)
+                .find_frequent_patterns();
+            let result = FPGrowth::<&str>::new(transactions.clone(), minimum_support)
+                .with_item_priority(item_priority.clone())
+                .find_frequent_patterns();
+            assert_eq!(sorted_patterns(&expected), sorted_patterns(&result));
+        }
+    }
+
+    #[test]
+    fn test_elimination_sets_sorted() {
+        let elimination_sets: HashSet<Vec<&str>> = vec![
+            vec!["b", "a"],
+            vec!["c"],
+            vec!["a", "c", "d"],
+            vec!["a", "b"],
+            vec!["a"],
+        ]
+        .into_iter()
+        .collect();
+        let result = FPResult::new(vec![], elimination_sets);
+        assert_eq!(
+            result.elimination_sets_sorted(),
+            vec![
+                vec!["a"],
+                vec!["c"],
+                vec!["a", "b"],
+                vec!["b", "a"],
+                vec!["a", "c", "d"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_frequent_patterns_explained() {
+        let transactions = small_fixture_transactions();
+        let fp_growth_str = FPGrowth::<&str>::new(transactions, 2);
+        let explanations = fp_growth_str.find_frequent_patterns_explained();
+        assert_eq!(
+            explanations.len(),
+            fp_growth_str
+                .find_frequent_patterns()
+                .frequent_patterns_num()
+        );
+        for explanation in explanations.iter() {
+            let support: usize = explanation
+                .paths()
+                .iter()
+                .map(|path| path.last().unwrap().1)
+                .sum();
+            assert_eq!(support, explanation.support());
+            for path in explanation.paths().iter() {
+                for item in explanation.pattern().iter() {
+                    assert!(path.iter().any(|(path_item, _)| path_item == item));
+                }
+            }
+        }
+        let explanation = explanations
+            .iter()
+            .find(|explanation| explanation.pattern() == ["d"])
+            .unwrap();
+        assert_eq!(
+            explanation.paths(),
+            [vec![("a", 4), ("d", 1)], vec![("c", 1), ("d", 1)]]
+        );
+    }
+
+    #[test]
+    fn test_send_and_sync() {
+        fn assert_send_and_sync<S: Send + Sync>() {}
+        assert_send_and_sync::<FPResult<&str>>();
+        assert_send_and_sync::<FPGrowth<&str>>();
+
+        // The configured instance is mined on another thread.
+        let fp_growth = FPGrowth::new(vec![vec![1, 2], vec![1, 3], vec![1, 2]], 2)
+            .with_item_normalizer(|item| item % 10)
+            .with_support_predicate(|support, _| support >= 2)
+            .with_item_exclusion(|&item| item > 5)
+            .with_node_pool();
+        let result = std::thread::spawn(move || fp_growth.find_frequent_patterns())
+            .join()
+            .unwrap();
+        assert_eq!(
+            sorted_patterns(&result),
+            vec![(vec![1], 3), (vec![1, 2], 2), (vec![2], 2)]
+        );
+    }
+
+    #[test]
+    fn test_support_of() {
+        let transactions = small_fixture_transactions();
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        for (pattern, support) in result.frequent_patterns().iter() {
+            assert_eq!(result.support_of(pattern), Some(*support));
+        }
+        assert_eq!(result.support_of(&["c", "b", "a"]), Some(2));
+        assert_eq!(result.support_of(&["b", "a", "b"]), Some(3));
+        assert_eq!(result.support_of(&["a", "d"]), None);
+        assert_eq!(result.support_of(&["e"]), None);
+        assert_eq!(result.support_of(&[]), None);
+    }
+
+    #[test]
+    fn test_coverage() {
+        let train = small_fixture_transactions();
+        let result = FPGrowth::<&str>::new(train, 2).find_frequent_patterns();
+        let test = vec![
+            vec!["b", "a", "e"],
+            vec!["a", "d"],
+            vec!["c"],
+            vec!["c", "e", "b"],
+        ];
+        assert_eq!(result.coverage(&test), 0.5);
+        assert_eq!(result.coverage(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_closure_families() {
+        let transactions = small_fixture_transactions();
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        let families = result.closure_families();
+        let families: Vec<_> = families
+            .iter()
+            .map(|family| {
+                (
+                    family.closed_pattern().to_vec(),
+                    family.support(),
+                    family.sub_patterns().to_vec(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            families,
+            vec![
+                (vec!["a"], 4, vec![]),
+                (vec!["a", "b"], 3, vec![vec!["b"]]),
+                (vec!["c"], 3, vec![]),
+                (vec!["a", "b", "c"], 2, vec![vec!["a", "c"], vec!["b", "c"]]),
+                (vec!["d"], 2, vec![]),
+            ]
+        );
+        assert_eq!(result.closed_patterns().len(), 5);
+    }
+
+    #[test]
+    fn test_tree_malformed_input() {
+        let mut tree = Tree::<&str>::new();
+        assert!(tree.generate_prefix_path("a").is_empty());
+        assert!(tree.get_all_nodes("a").is_empty());
+        tree.add_transaction(vec![]);
+        tree.add_transaction(vec!["a", "b"]);
+        tree.add_transaction(vec!["a", "a"]);
+        assert!(tree.generate_prefix_path("c").is_empty());
+
+        let partial_tree = Tree::<&str>::generate_partial_tree(&[]);
+        assert_eq!(partial_tree.node_count(), 0);
+        let root_node = Node::<&str>::new_rc(None, 0);
+        let partial_tree = Tree::generate_partial_tree(&[
+            vec![],
+            vec![Rc::clone(&root_node)],
+            vec![Rc::clone(&root_node), Node::new_rc(Some("a"), 2)],
+        ]);
+        assert_eq!(partial_tree.node_count(), 1);
+        assert_eq!(partial_tree.get_all_nodes("a")[0].count(), 2);
+        let partial_tree = Tree::generate_partial_tree(&tree.generate_prefix_path("b"));
+        assert_eq!(partial_tree.node_count(), 2);
+        assert_eq!(
+            FPGrowth::mine_existing_tree(&partial_tree, 1).frequent_patterns_num(),
+            3
+        );
+
+        let child_node = Node::new_rc(Some("a"), 1);
+        assert_eq!(root_node.remove_child(Rc::clone(&child_node)), None);
+        root_node.add_child(Rc::clone(&child_node));
+        assert_eq!(
+            root_node.remove_child(Rc::clone(&child_node)),
+            Some(Rc::clone(&child_node))
+        );
+        assert!(root_node.is_leaf());
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let transactions = vec![
+            vec!["e", "c", "a", "b", "f", "h"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["e", "c", "a", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+            vec!["g", "c", "e", "a"],
+            vec!["a", "c", "e", "g"],
+            vec!["i"],
+        ];
+        let result = FPGrowth::<&str>::new(transactions.clone(), 2).find_frequent_patterns();
+        for max_depth in 0..=6 {
+            let limited_result = FPGrowth::<&str>::new(transactions.clone(), 2)
+                .with_max_depth(max_depth)
+                .find_frequent_patterns();
+            let expected: Vec<(Vec<&str>, usize)> = sorted_patterns(&result)
+                .into_iter()
+                .filter(|(pattern, _)| pattern.len() <= max_depth)
+                .collect();
+            assert_eq!(sorted_patterns(&limited_result), expected);
+        }
+    }
+
+    #[test]
+    fn test_all_confidence() {
+        let transactions = small_fixture_transactions();
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        assert_eq!(result.all_confidence(&["a"]), Some(1.0));
+        assert_eq!(result.all_confidence(&["b", "a"]), Some(0.75));
+        assert_eq!(result.all_confidence(&["a", "b", "c"]), Some(0.5));
+        assert_eq!(result.all_confidence(&["a", "d"]), None);
+        assert_eq!(result.all_confidence(&[]), None);
+
+        let result = FPResult::new(
+            vec![(vec!["a"], 4), (vec!["b"], 2), (vec!["a", "b"], 2)],
+            HashSet::new(),
+        );
+        assert_eq!(result.all_confidence(&["a", "b"]), Some(0.5));
+    }
+
+    #[test]
+    fn test_from_patterns() {
+        let patterns = vec![(vec!["a"], 4), (vec!["b"], 2), (vec!["a", "b"], 2)];
+        let result = FPResult::from_patterns(patterns.clone());
+        assert_eq!(result.frequent_patterns_num(), 3);
+        assert_eq!(result.elimination_sets_num(), 0);
+        assert_eq!(
+            sorted_patterns(&result),
+            sorted_patterns(&FPResult::new(patterns, HashSet::new()))
+        );
+        assert_eq!(result.support_of(&["b", "a"]), Some(2));
+    }
+
+    #[test]
+    fn test_support_satisfiable() {
+        let transactions = vec![vec!["a", "b"], vec!["a"], vec!["b", "c"]];
+        assert!(FPGrowth::<&str>::new(transactions.clone(), 3).is_support_satisfiable());
+        assert!(!FPGrowth::<&str>::new(transactions.clone(), 4).is_support_satisfiable());
+        assert!(FPGrowth::<&str>::try_new(transactions.clone(), 3).is_ok());
+        assert_eq!(
+            FPGrowth::<&str>::try_new(transactions, 4).err(),
+            Some(Error::SupportExceedsTransactions {
+                minimum_support: 4,
+                transactions_num: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_long_format() {
+        let result = FPResult::from_patterns(vec![(vec!["a"], 4), (vec!["b", "a"], 3)]);
+        let (membership_rows, support_rows) = result.to_long_format();
+        assert_eq!(membership_rows, vec![(0, "a"), (1, "a"), (1, "b")]);
+        assert_eq!(support_rows, vec![(0, 4), (1, 3)]);
+    }
+
+    #[test]
+    fn test_generate_synthetic_transactions() {
+        let transactions = generate_synthetic_transactions(100, 20, 4, 7);
+        assert_eq!(transactions.len(), 100);
+        assert_eq!(transactions, generate_synthetic_transactions(100, 20, 4, 7));
+        assert_ne!(transactions, generate_synthetic_transactions(100, 20, 4, 8));
+        for transaction in transactions.iter() {
+            assert!(!transaction.is_empty() && transaction.len() <= 7);
+            assert!(transaction.iter().all(|&item| item < 20));
+            let mut items = transaction.clone();
+            items.sort_unstable();
+            items.dedup();
+            assert_eq!(items.len(), transaction.len());
+        }
+    }
+
+    #[test]
+    fn test_random_transactions() {
+        for seed in 0..5 {
+            let transactions = random_transactions(201, 50, 4, seed);
+            assert_eq!(transactions, random_transactions(201, 50, 4, seed));
+            let planted_itemset = planted_itemset(50, seed);
+            assert_eq!(planted_itemset.len(), 3);
+            let result = FPGrowth::<u32>::new(transactions, 101).find_frequent_patterns();
+            assert!(result.support_of(&planted_itemset).unwrap() >= 101);
+        }
+        assert_eq!(planted_itemset(2, 0).len(), 2);
+    }
+
+    #[test]
+    fn test_downward_closure_property() {
+        // A property test over the random transactions and thresholds: every subset of a
+        // frequent pattern must be frequent with a support not lower than the pattern's.
+        let mut rng = SplitMix64::new(2021);
+        for seed in 0..20 {
+            let num = 20 + rng.next_below(80);
+            let num_items = 2 + rng.next_below(15);
+            let avg_len = 1 + rng.next_below(6);
+            let transactions = random_transactions(num, num_items, avg_len, seed);
+            let minimum_support = 1 + rng.next_below(num / 4);
+            let result =
+                FPGrowth::<u32>::new(transactions, minimum_support).find_frequent_patterns();
+            for (pattern, support) in result.frequent_patterns().iter() {
+                assert!(*support >= minimum_support);
+                if pattern.len() < 2 {
+                    continue;
+                }
+                for index in 0..pattern.len() {
+                    let item_support = result.support_of(&[pattern[index]]).unwrap();
+                    assert!(item_support >= *support);
+                    let mut subset = pattern.clone();
+                    subset.remove(index);
+                    let subset_support = result.support_of(&subset).unwrap();
+                    assert!(subset_support >= *support);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rank_by() {
+        let transactions = small_fixture_transactions();
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        assert_eq!(result.transactions_num(), 5);
+        assert_eq!(
+            result.interest_of(&["a", "b"], InterestMeasure::RelativeSupport),
+            Some(0.6)
+        );
+        // lift(a, b) = (3 / 5) / ((4 / 5) * (3 / 5))
+        let lift = result
+            .interest_of(&["a", "b"], InterestMeasure::Lift)
+            .unwrap();
+        assert!((lift - 1.25).abs() < 1e-9);
+        assert_eq!(result.interest_of(&["a", "d"], InterestMeasure::Lift), None);
+
+        let ranked_patterns = result.rank_by(InterestMeasure::Support);
+        assert_eq!(ranked_patterns.len(), result.frequent_patterns_num());
+        assert_eq!(ranked_patterns[0], (vec!["a"], 4.0));
+        let ranked_patterns = result.rank_by(InterestMeasure::Lift);
+        assert_eq!(ranked_patterns[0].0.len(), 3);
+        assert!(ranked_patterns
+            .windows(2)
+            .all(|pair| pair[0].1 >= pair[1].1));
+
+        // The measures need the number of transactions are not available for a manual result.
+        let result =
+            FPResult::from_patterns(vec![(vec!["a"], 4), (vec!["b"], 2), (vec!["a", "b"], 2)]);
+        assert_eq!(result.rank_by(InterestMeasure::AllConfidence).len(), 3);
+        assert!(result.rank_by(InterestMeasure::RelativeSupport).is_empty());
+    }
+
+    #[test]
+    fn test_top_correlated_pairs() {
+        let result =
+            FPGrowth::<&str>::new(small_fixture_transactions(), 2).find_frequent_patterns();
+        // lift(a, b) = 1.25 and lift(b, c) = (2 / 5) / ((3 / 5) * (3 / 5)), lift(a, c) is lower
+        // than 1.0.
+        let top_pairs = result.top_correlated_pairs(2, InterestMeasure::Lift);
+        assert_eq!(top_pairs.len(), 2);
+        assert_eq!(top_pairs[0].0, ("a", "b"));
+        assert!((top_pairs[0].1 - 1.25).abs() < 1e-9);
+        assert_eq!(top_pairs[1].0, ("b", "c"));
+        assert!((top_pairs[1].1 - 10.0 / 9.0).abs() < 1e-9);
+        assert!(result
+            .top_correlated_pairs(0, InterestMeasure::Lift)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_from_disk() {
+        let transactions = random_transactions(200, 30, 6, 9);
+        let path = std::env::temp_dir().join(format!("fp-growth-{}.txt", std::process::id()));
+        let content: String = transactions
+            .iter()
+            .map(|transaction| {
+                let items: Vec<String> = transaction.iter().map(|item| item.to_string()).collect();
+                items.join(" ") + "\n"
+            })
+            .collect();
+        fs::write(&path, content).unwrap();
+
+        let disk_result = FPGrowth::from_disk(&path, 20)
+            .unwrap()
+            .find_frequent_patterns();
+        let memory_result = FPGrowth::new(transactions, 20).find_frequent_patterns();
+        assert_eq!(disk_result.transactions_num(), 200);
+        assert_eq!(
+            sorted_patterns(&disk_result),
+            sorted_patterns(&memory_result)
+        );
+        assert_eq!(
+            disk_result.elimination_sets_sorted(),
+            memory_result.elimination_sets_sorted()
+        );
+
+        fs::write(&path, "1 2 3\n4 x\n").unwrap();
+        let err = FPGrowth::from_disk(&path, 1).err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        // The lines appended after the file is opened are ignored.
+        fs::write(&path, "1 2\n1 2\n").unwrap();
+        let fp_growth = FPGrowth::from_disk(&path, 2).unwrap();
+        fs::write(&path, "1 2\n1 2\n1 3\n1 3\n").unwrap();
+        assert_eq!(
+            sorted_patterns(&fp_growth.find_frequent_patterns()),
+            vec![(vec![1], 2), (vec![1, 2], 2), (vec![2], 2)]
+        );
+        // A file which fails to be parsed or opened again is caught in every build.
+        fs::write(&path, "1 2\n1 x\n").unwrap();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            fp_growth.find_frequent_patterns()
+        }));
+        assert!(result.is_err());
+        fs::remove_file(&path).unwrap();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            fp_growth.find_frequent_patterns()
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_other_bucket() {
+        let transactions = vec![
+            vec!["a", "b", "x"],
+            vec!["a", "y", "z"],
+            vec!["a", "b"],
+            vec!["b", "w"],
+        ];
+        let result = FPGrowth::<&str>::new(transactions.clone(), 2)
+            .with_other_bucket("OTHER")
+            .find_frequent_patterns();
+        let patterns = sorted_patterns(&result);
+        // "y" and "z" are replaced by a single "OTHER" in the second transaction.
+        assert!(patterns.contains(&(vec!["OTHER"], 3)));
+        assert!(patterns.contains(&(vec!["OTHER", "a"], 2)));
+        assert!(patterns.contains(&(vec!["OTHER", "b"], 2)));
+        assert!(patterns.iter().all(|(pattern, _)| !pattern.contains(&"x")));
+
+        // Every transaction holds an infrequent item, so only the other bucket is left.
+        let result = FPGrowth::<&str>::new(transactions.clone(), 4)
+            .with_other_bucket("OTHER")
+            .find_frequent_patterns();
+        assert_eq!(sorted_patterns(&result), vec![(vec!["OTHER"], 4)]);
+
+        // The other bucket itself is dropped if it is not frequent either.
+        let transactions = vec![vec!["a", "b"], vec!["a", "b"], vec!["b", "w"]];
+        let result = FPGrowth::<&str>::new(transactions, 2)
+            .with_other_bucket("OTHER")
+            .find_frequent_patterns();
+        assert_eq!(
+            sorted_patterns(&result),
+            vec![(vec!["a"], 2), (vec!["a", "b"], 2), (vec!["b"], 3)]
+        );
+
+        // The memory budget raises the minimum support above the other bucket as well.
+        let fp_growth = FPGrowth::<&str>::new(vec![vec!["a"], vec!["b"]], 1)
+            .with_other_bucket("OTHER")
+            .with_memory_budget(0);
+        assert_eq!(
+            fp_growth.find_frequent_patterns().frequent_patterns_num(),
+            0
+        );
+        let transactions = vec![
+            vec!["a", "x"],
+            vec!["a", "y"],
+            vec!["a", "b"],
+            vec!["b", "z"],
+        ];
+        let fp_growth = FPGrowth::<&str>::new(transactions, 1)
+            .with_other_bucket("OTHER")
+            .with_memory_budget(2);
+        assert_eq!(
+            sorted_patterns(&fp_growth.find_frequent_patterns()),
+            vec![(vec!["OTHER"], 4), (vec!["OTHER", "a"], 3), (vec!["a"], 3)]
+        );
+    }
+
+    #[test]
+    fn test_remove_node_ancestor_counts() {
+        let mut tree = Tree::<&str>::new();
+        tree.add_transaction(vec!["a", "b", "c"]);
+        tree.add_transaction(vec!["a", "b", "c"]);
+        tree.add_transaction(vec!["a", "b", "d"]);
+        tree.add_transaction(vec!["a"]);
+        let a_node = tree.get_all_nodes("a")[0].clone();
+        let b_node = tree.get_all_nodes("b")[0].clone();
+        assert_eq!((a_node.count(), b_node.count()), (4, 3));
+
+        assert!(tree.remove_node(&tree.get_all_nodes("c")[0].clone()));
+        assert_eq!((a_node.count(), b_node.count()), (2, 1));
+        assert_eq!(tree.itemset_support(&["a", "b"]), 1);
+        // The ancestor whose transactions are all removed is kept with count 0.
+        assert!(tree.remove_node(&tree.get_all_nodes("d")[0].clone()));
+        assert_eq!((a_node.count(), b_node.count()), (1, 0));
+        assert_eq!(tree.node_count(), 2);
+    }
+
+    #[test]
+    fn test_remove_node() {
+        let mut tree = Tree::<&str>::new();
+        tree.add_transaction(vec!["a", "b", "c"]);
+        tree.add_transaction(vec!["b", "c"]);
+        tree.add_transaction(vec!["a", "c"]);
+        tree.add_transaction(vec!["c"]);
+        assert_eq!(tree.node_count(), 7);
+        let c_nodes = tree.get_all_nodes("c");
+        assert_eq!(c_nodes.len(), 4);
+
+        // Remove a node in the middle of the neighbor chain.
+        assert!(tree.remove_node(&c_nodes[1]));
+        assert_eq!(tree.node_count(), 6);
+        let remaining_nodes = tree.get_all_nodes("c");
+        assert_eq!(remaining_nodes.len(), 3);
+        assert!(remaining_nodes
+            .iter()
+            .all(|node| !Rc::ptr_eq(node, &c_nodes[1])));
+        assert!(!tree.remove_node(&c_nodes[1]));
+
+        // Remove the tail, then a subtree holding the head.
+        assert!(tree.remove_node(&c_nodes[3]));
+        let a_node = tree.get_all_nodes("a")[0].clone();
+        assert!(tree.remove_node(&a_node));
+        assert_eq!(tree.node_count(), 1);
+        assert!(tree.get_all_nodes("a").is_empty());
+        assert!(tree.get_all_nodes("c").is_empty());
+        assert_eq!(tree.get_all_nodes("b").len(), 1);
+
+        // The route is still extended correctly after the surgery.
+        tree.add_transaction(vec!["c"]);
+        tree.add_transaction(vec!["b", "c"]);
+        assert_eq!(tree.get_all_nodes("c").len(), 2);
+        assert_eq!(tree.generate_prefix_path("c").len(), 2);
+    }
+
+    #[test]
+    fn test_rank_by_item_weights() {
+        let result = FPResult::from_patterns(vec![
+            (vec!["a"], 4),
+            (vec!["b"], 3),
+            (vec!["c"], 1),
+            (vec!["a", "b"], 2),
+            (vec!["a", "c"], 1),
+        ]);
+        let weights: HashMap<&str, f64> = vec![("a", 1.0), ("b", 2.0)].into_iter().collect();
+
+        let ranked_patterns = result.rank_by_item_weights(&weights, Combine::Sum);
+        assert_eq!(ranked_patterns[0], (vec!["b"], 6.0));
+        assert_eq!(ranked_patterns[1], (vec!["a", "b"], 6.0));
+        assert_eq!(ranked_patterns[2], (vec!["a"], 4.0));
+        assert_eq!(ranked_patterns.len(), 5);
+
+        let ranked_patterns = result.rank_by_item_weights(&weights, Combine::Product);
+        assert_eq!(ranked_patterns[3].1, 0.0);
+        assert_eq!(ranked_patterns[4].1, 0.0);
+        let ranked_patterns = result.rank_by_item_weights(&weights, Combine::Mean);
+        assert_eq!(ranked_patterns[0], (vec!["b"], 6.0));
+        assert_eq!(ranked_patterns[1], (vec!["a"], 4.0));
+        assert_eq!(ranked_patterns[2], (vec!["a", "b"], 3.0));
+    }
+
+    #[test]
+    fn test_bitset_transactions() {
+        let transactions = random_transactions(300, 100, 20, 3);
+        let bitset_transactions =
+            BitsetTransactions::from_transactions(&transactions, 100).unwrap();
+        assert_eq!(bitset_transactions.transactions_num(), 300);
+
+        let bitset_result =
+            FPGrowth::from_source(bitset_transactions.clone(), 40).find_frequent_patterns();
+        let result = FPGrowth::new(transactions.clone(), 40).find_frequent_patterns();
+        assert_eq!(sorted_patterns(&bitset_result), sorted_patterns(&result));
+        for (pattern, support) in result.frequent_patterns().iter().take(20) {
+            assert_eq!(
+                bitset_transactions.transactions_containing(pattern),
+                *support
+            );
+        }
+        assert_eq!(
+            result.bitset_coverage(&bitset_transactions),
+            result.coverage(&transactions)
+        );
+
+        let mut bitset_transactions = BitsetTransactions::new(8);
+        bitset_transactions.push(&[1, 7, 1]).unwrap();
+        assert_eq!(
+            bitset_transactions.push(&[2, 8]),
+            Err(Error::ItemOutOfUniverse {
+                item: 8,
+                universe_size: 8
+            })
+        );
+        assert_eq!(bitset_transactions.transactions_num(), 1);
+        assert_eq!(bitset_transactions.transactions_containing(&[1, 7]), 1);
+        assert_eq!(bitset_transactions.transactions_containing(&[9]), 0);
+    }
+
+    #[test]
+    fn test_average_pattern_length() {
+        let result =
+            FPResult::from_patterns(vec![(vec!["a"], 4), (vec!["b"], 3), (vec!["a", "b"], 2)]);
+        assert!((result.average_pattern_length() - 4.0 / 3.0).abs() < 1e-9);
+        assert_eq!(
+            FPResult::<&str>::from_patterns(vec![]).average_pattern_length(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_itemset_support_repeated_items() {
+        let mut tree = Tree::<&str>::new();
+        tree.add_transaction(vec!["a", "a", "b"]);
+        tree.add_transaction(vec!["b", "b", "a"]);
+        tree.add_transaction(vec!["b", "b", "c"]);
+        tree.add_transaction(vec!["a"]);
+        assert_eq!(tree.itemset_support(&["a"]), 3);
+        assert_eq!(tree.itemset_support(&["a", "a"]), 3);
+        assert_eq!(tree.itemset_support(&["b"]), 3);
+        assert_eq!(tree.itemset_support(&["b", "a", "b"]), 2);
+        assert_eq!(tree.itemset_support(&["a", "b", "c"]), 0);
+        assert_eq!(tree.itemset_support(&["b", "c"]), 1);
+        let frozen_tree = tree.freeze();
+        assert_eq!(frozen_tree.itemset_support(&["a"]), 3);
+        assert_eq!(frozen_tree.itemset_support(&["b", "a", "b"]), 2);
+    }
+
+    #[test]
+    fn test_itemset_support() {
+        let transactions = [
+            vec!["a", "b", "c"],
+            vec!["b", "c"],
+            vec!["c", "a"],
+            vec!["a", "b"],
+            vec!["d"],
+        ];
+        let mut tree = Tree::<&str>::new();
+        for transaction in transactions.iter() {
+            tree.add_transaction(transaction.clone());
+        }
+        // The items are inserted in different orders.
+        assert_eq!(tree.itemset_support(&["a", "c"]), 2);
+        assert_eq!(tree.itemset_support(&["c", "a", "a"]), 2);
+        assert_eq!(tree.itemset_support(&["a", "b", "c"]), 1);
+        assert_eq!(tree.itemset_support(&["b"]), 3);
+        assert_eq!(tree.itemset_support(&["a", "d"]), 0);
+        assert_eq!(tree.itemset_support(&["e"]), 0);
+        assert_eq!(tree.itemset_support(&[]), 5);
+
+        // It agrees with the brute-force count on random transactions.
+        let transactions = random_transactions(200, 20, 6, 5);
+        let mut tree = Tree::<u32>::new();
+        for transaction in transactions.iter() {
+            let mut transaction = transaction.clone();
+            transaction.sort_unstable();
+            transaction.dedup();
+            tree.add_transaction(transaction);
+        }
+        let itemset = planted_itemset(20, 5);
+        let expected = transactions
+            .iter()
+            .filter(|transaction| itemset.iter().all(|item| transaction.contains(item)))
+            .count();
+        assert_eq!(tree.itemset_support(&itemset), expected);
+    }
+
+    #[test]
+    fn test_find_top_k_closed_patterns() {
+        let transactions = random_transactions(300, 25, 6, 11);
+        let fp_growth = FPGrowth::<u32>::new(transactions, 6);
+        let closed_patterns = fp_growth.find_frequent_patterns().closed_patterns();
+        for k in [0, 1, 5, 20, 100] {
+            let top_k = fp_growth.find_top_k_closed_patterns(k);
+            assert_eq!(top_k.len(), k.min(closed_patterns.len()));
+            assert_eq!(top_k, closed_patterns[..top_k.len()].to_vec());
+        }
+        let all = fp_growth.find_top_k_closed_patterns(usize::MAX);
+        assert_eq!(all, closed_patterns);
+    }
+
+    #[test]
+    fn test_remine_with_lower_support() {
+        let transactions = random_transactions(200, 20, 6, 13);
+        let previous = FPGrowth::<u32>::new(transactions.clone(), 30).find_frequent_patterns();
+        let fp_growth = FPGrowth::<u32>::new(transactions, 10);
+
+        let result = fp_growth.remine_with_lower_support(&previous, 10);
+        let expected_10 = fp_growth.find_frequent_patterns();
+        assert_eq!(sorted_patterns(&result), sorted_patterns(&expected_10));
+        assert_eq!(result.transactions_num(), 200);
+
+        // Raising the threshold only filters the previous patterns.
+        let raised = fp_growth.remine_with_lower_support(&result, 40);
+        let expected =
+            FPGrowth::<u32>::new(random_transactions(200, 20, 6, 13), 40).find_frequent_patterns();
+        assert_eq!(sorted_patterns(&raised), sorted_patterns(&expected));
+        for (pattern, support) in expected.frequent_patterns() {
+            assert_eq!(raised.support_of(&pattern), Some(support));
+        }
+
+        // A result without the item supports has them counted again.
+        let manual = FPResult::from_patterns(previous.frequent_patterns());
+        let result = fp_growth.remine_with_lower_support(&manual, 10);
+        assert_eq!(sorted_patterns(&result), sorted_patterns(&expected_10));
+        assert_eq!(result.item_support(0), expected_10.item_support(0));
+    }
+
+    #[test]
+    fn test_giant_transaction() {
+        // A single deep path is dropped without overflowing the stack.
+        let mut tree = Tree::<u32>::new();
+        tree.add_transaction((0..200_000).collect());
+        tree.add_transaction(vec![0, 1]);
+        assert_eq!(tree.node_count(), 200_000);
+        assert_eq!(tree.itemset_support(&[0, 199_999]), 1);
+        drop(tree);
+
+        // A catch-all transaction holds every item three times, the others hold one or two.
+        let items_num = 500;
+        let mut giant_transaction: Vec<u32> = (0..items_num).collect();
+        giant_transaction.extend(0..items_num);
+        giant_transaction.extend((0..items_num).rev());
+        let mut transactions = vec![giant_transaction];
+        transactions.extend((0..items_num).map(|item| vec![item]));
+        transactions.extend((0..10).map(|item| vec![item, item + 1]));
+
+        let result = FPGrowth::<u32>::new(transactions, 2).find_frequent_patterns();
+        // Every item is frequent, and so are the pairs found in more than the giant transaction.
+        assert_eq!(result.frequent_patterns_num(), items_num as usize + 10);
+        assert_eq!(result.support_of(&[5]), Some(4));
+        assert_eq!(result.support_of(&[499]), Some(2));
+        assert_eq!(result.support_of(&[5, 6]), Some(2));
+        assert_eq!(result.support_of(&[5, 7]), None);
+    }
+
+    #[test]
+    fn test_mutex_groups() {
+        let transactions = vec![
+            vec!["a", "b", "c", "d"],
+            vec!["a", "b", "c"],
+            vec!["a", "c", "d"],
+            vec!["b", "c", "d"],
+        ];
+        let unconstrained = FPGrowth::<&str>::new(transactions.clone(), 2).find_frequent_patterns();
+        let mutex_groups = vec![vec!["a", "b"].into_iter().collect::<HashSet<&str>>()];
+        let result = FPGrowth::<&str>::new(transactions, 2)
+            .with_mutex_groups(mutex_groups)
+            .find_frequent_patterns();
+        let expected: Vec<_> = sorted_patterns(&unconstrained)
+            .into_iter()
+            .filter(|(pattern, _)| !(pattern.contains(&"a") && pattern.contains(&"b")))
+            .collect();
+        assert_eq!(sorted_patterns(&result), expected);
+        assert!(result.support_of(&["a", "b"]).is_none());
+        assert_eq!(result.support_of(&["a", "c", "d"]), Some(2));
+    }
+
+    #[test]
+    fn test_include_singletons() {
+        let transactions = random_transactions(100, 15, 5, 17);
+        let result = FPGrowth::<u32>::new(transactions.clone(), 5).find_frequent_patterns();
+        let without_singletons = FPGrowth::<u32>::new(transactions, 5)
+            .include_singletons(false)
+            .find_frequent_patterns();
+        assert!(without_singletons
+            .frequent_patterns()
+            .iter()
+            .all(|(pattern, _)| pattern.len() >= 2));
+        let expected: Vec<_> = sorted_patterns(&result)
+            .into_iter()
+            .filter(|(pattern, _)| pattern.len() >= 2)
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(sorted_patterns(&without_singletons), expected);
+    }
+
+    #[test]
+    fn test_maximal_pattern_count() {
+        let transactions = vec![
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["a", "d"],
+            vec!["e"],
+        ];
+        // {a, b, c} and {a, d} are the maximal patterns.
+        let fp_growth = FPGrowth::<&str>::new(transactions, 2);
+        assert_eq!(fp_growth.maximal_pattern_count(), 2);
+
+        let transactions = random_transactions(150, 20, 6, 21);
+        let fp_growth = FPGrowth::<u32>::new(transactions, 8);
+        let patterns = sorted_patterns(&fp_growth.find_frequent_patterns());
+        let expected = patterns
+            .iter()
+            .filter(|(pattern, _)| {
+                !patterns.iter().any(|(other, _)| {
+                    other.len() > pattern.len() && pattern.iter().all(|item| other.contains(item))
+                })
+            })
+            .count();
+        assert_eq!(fp_growth.maximal_pattern_count(), expected);
+    }
+
+    #[test]
+    fn test_pattern_compression_stats() {
+        let transactions = vec![
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["a", "d"],
+            vec!["e"],
+        ];
+        // {a}, {a, b, c} and {a, d} are the closed patterns, the last two are maximal.
+        let fp_growth = FPGrowth::<&str>::new(transactions, 2);
+        let stats = fp_growth.pattern_compression_stats();
+        assert_eq!(
+            (
+                stats.all_count(),
+                stats.closed_count(),
+                stats.maximal_count()
+            ),
+            (9, 3, 2)
+        );
+
+        let transactions = random_transactions(150, 20, 6, 21);
+        let fp_growth = FPGrowth::<u32>::new(transactions, 8);
+        let result = fp_growth.find_frequent_patterns();
+        let stats = fp_growth.pattern_compression_stats();
+        assert_eq!(stats.all_count(), result.frequent_patterns_num());
+        assert_eq!(stats.closed_count(), result.closed_patterns().len());
+        assert_eq!(stats.maximal_count(), fp_growth.maximal_pattern_count());
+    }
+
+    #[test]
+    fn test_find_frequent_patterns_budgeted() {
+        let transactions = random_transactions(150, 20, 6, 23);
+        let fp_growth = FPGrowth::<u32>::new(transactions, 8);
+        let result = fp_growth.find_frequent_patterns();
+        assert!(result.is_complete());
+
+        let partial_result = fp_growth.find_frequent_patterns_budgeted(3);
+        assert!(!partial_result.is_complete());
+        let mut item_supports: Vec<(usize, u32)> = result
+            .frequent_patterns()
+            .into_iter()
+            .filter(|(pattern, _)| pattern.len() == 1)
+            .map(|(pattern, support)| (usize::MAX - support, pattern[0]))
+            .collect();
+        item_supports.sort();
+        let processed_items: Vec<u32> = item_supports
+            .iter()
+            .take(3)
+            .map(|(_, item)| *item)
+            .collect();
+        // Exactly the patterns made up of the three most frequent items are found.
+        let expected: Vec<_> = sorted_patterns(&result)
+            .into_iter()
+            .filter(|(pattern, _)| pattern.iter().all(|item| processed_items.contains(item)))
+            .collect();
+        assert_eq!(sorted_patterns(&partial_result), expected);
+        assert_eq!(expected.len(), 7);
+        assert_eq!(
+            sorted_patterns(&fp_growth.find_frequent_patterns_budgeted(3)),
+            expected
+        );
+
+        let full_result = fp_growth.find_frequent_patterns_budgeted(usize::MAX);
+        assert!(full_result.is_complete());
+        assert_eq!(sorted_patterns(&full_result), sorted_patterns(&result));
+    }
+
+    #[test]
+    fn test_itemset() {
+        let itemset = Itemset::from(vec!["c", "a", "b", "a"]);
+        assert_eq!(itemset.items(), &["a", "b", "c"]);
+        assert_eq!(itemset, Itemset::from(vec!["b", "c", "a"]));
+        assert!(itemset.contains(&"b"));
+        assert!(Itemset::from(vec!["c", "a"]).is_subset(&itemset));
+        assert!(!Itemset::from(vec!["d"]).is_subset(&itemset));
+        let itemsets: HashSet<Itemset<&str>> =
+            vec![itemset.clone(), Itemset::from(vec!["b", "a", "c"])]
+                .into_iter()
+                .collect();
+        assert_eq!(itemsets.len(), 1);
+
+        let result = FPResult::from_patterns(vec![(vec!["b", "a"], 3), (vec!["a"], 4)]);
+        assert_eq!(
+            result.itemsets(),
+            &[
+                (Itemset::from(vec!["a", "b"]), 3),
+                (Itemset::from(vec!["a"]), 4)
+            ]
+        );
+        assert_eq!(result.frequent_patterns()[0], (vec!["a", "b"], 3));
+    }
+
+    #[test]
+    fn test_frozen_tree() {
+        let transactions = random_transactions(200, 20, 6, 29);
+        let mut tree = Tree::<u32>::new();
+        for transaction in transactions.iter() {
+            let mut transaction = transaction.clone();
+            transaction.sort_unstable();
+            transaction.dedup();
+            tree.add_transaction(transaction);
+        }
+        let itemsets: Vec<Vec<u32>> = vec![vec![], vec![3], vec![1, 2], planted_itemset(20, 29)];
+        let expected: Vec<usize> = itemsets
+            .iter()
+            .map(|itemset| tree.itemset_support(itemset))
+            .collect();
+        let header_rows = tree.header_summary();
+        let node_count = tree.node_count();
+        let item_node_counts: Vec<usize> = tree
+            .get_all_nodes(3)
+            .iter()
+            .map(|node| node.count())
+            .collect();
+
+        let frozen_tree: Arc<FrozenTree<u32>> = Arc::new(tree.freeze());
+        assert_eq!(frozen_tree.node_count(), node_count);
+        assert_eq!(frozen_tree.header_summary(), header_rows);
+        assert_eq!(frozen_tree.item_node_counts(3), item_node_counts);
+        let handles: Vec<_> = itemsets
+            .into_iter()
+            .map(|itemset| {
+                let frozen_tree = Arc::clone(&frozen_tree);
+                thread::spawn(move || frozen_tree.itemset_support(&itemset))
+            })
+            .collect();
+        let supports: Vec<usize> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+        assert_eq!(supports, expected);
+    }
+
+    #[test]
+    fn test_item_normalizer() {
+        let transactions = vec![
+            vec!["Milk", "bread"],
+            vec!["milk", "Bread", "BREAD"],
+            vec!["MILK", "eggs"],
+        ];
+        let normalize = |item: &'static str| match item.to_lowercase().as_str() {
+            "milk" => "milk",
+            "bread" => "bread",
+            _ => item,
+        };
+        let result = FPGrowth::<&str>::new(transactions.clone(), 2)
+            .with_item_normalizer(normalize)
+            .find_frequent_patterns();
+        assert_eq!(
+            sorted_patterns(&result),
+            vec![
+                (vec!["bread"], 2),
+                (vec!["bread", "milk"], 2),
+                (vec!["milk"], 3)
+            ]
+        );
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        assert_eq!(result.frequent_patterns_num(), 0);
+    }
+
+    #[test]
+    fn test_lattice_to_dot() {
+        let result =
+            FPResult::from_patterns(vec![(vec!["b", "a"], 2), (vec!["a"], 4), (vec!["b"], 2)]);
+        let dot = result.lattice_to_dot();
+        assert_eq!(
+            dot,
+            "digraph lattice {\n    rankdir=BT;\n\
+             \x20   0 [label=\"{\\\"a\\\"}\\n4\", style=filled, fillcolor=\"0.6 1.000 1.0\"];\n\
+             \x20   1 [label=\"{\\\"b\\\"}\\n2\", style=filled, fillcolor=\"0.6 0.500 1.0\"];\n\
+             \x20   2 [label=\"{\\\"a\\\", \\\"b\\\"}\\n2\", style=filled, fillcolor=\"0.6 0.500 1.0\"];\n\
+             \x20   1 -> 2;\n\
+             \x20   0 -> 2;\n\
+             }\n"
+        );
+        assert_eq!(
+            FPResult::<u32>::from_patterns(vec![]).lattice_to_dot(),
+            "digraph lattice {\n    rankdir=BT;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_reconstruct_transactions() {
+        let mut tree = Tree::<&str>::new();
+        for transaction in [
+            vec!["a", "b", "c"],
+            vec!["a", "b"],
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["b"],
+            vec![],
+        ] {
+            tree.add_transaction(transaction);
+        }
+        assert_eq!(
+            tree.reconstruct_transactions(),
+            vec![
+                (vec!["a", "b"], 1),
+                (vec!["a", "b", "c"], 2),
+                (vec!["a", "d"], 1),
+                (vec!["b"], 1),
+            ]
+        );
+
+        // The tree encodes the transactions losslessly.
+        let mut transactions: Vec<Vec<u32>> = random_transactions(100, 10, 4, 31)
+            .into_iter()
+            .map(|mut transaction| {
+                transaction.sort_unstable();
+                transaction.dedup();
+                transaction
+            })
+            .collect();
+        let mut tree = Tree::<u32>::new();
+        for transaction in transactions.iter() {
+            tree.add_transaction(transaction.clone());
+        }
+        let mut reconstructed_transactions: Vec<Vec<u32>> = tree
+            .reconstruct_transactions()
+            .into_iter()
+            .flat_map(|(transaction, count)| vec![transaction; count])
+            .collect();
+        transactions.retain(|transaction| !transaction.is_empty());
+        transactions.sort();
+        reconstructed_transactions.sort();
+        assert_eq!(reconstructed_transactions, transactions);
+    }
+
+    #[test]
+    fn test_transaction_containers() {
+        let transactions = vec![
+            vec!["a", "b", "c"],
+            vec!["a", "b"],
+            vec!["a", "c"],
+            vec!["b", "c", "b"],
+        ];
+        let expected =
+            sorted_patterns(&FPGrowth::new(transactions.clone(), 2).find_frequent_patterns());
+
+        let btree_sets: Vec<BTreeSet<&str>> = transactions
+            .iter()
+            .map(|transaction| transaction.iter().copied().collect())
+            .collect();
+        let result = FPGrowth::from_source(btree_sets, 2).find_frequent_patterns();
+        assert_eq!(sorted_patterns(&result), expected);
+
+        let hash_sets: Vec<HashSet<&str>> = transactions
+            .iter()
+            .map(|transaction| transaction.iter().copied().collect())
+            .collect();
+        let result = FPGrowth::from_source(hash_sets, 2).find_frequent_patterns();
+        assert_eq!(sorted_patterns(&result), expected);
+
+        struct Basket {
+            products: Vec<&'static str>,
+        }
+        impl Transaction<&'static str> for Basket {
+            fn items(&self) -> impl Iterator<Item = &'static str> {
+                self.products.iter().copied()
+            }
+        }
+        let baskets: Vec<Basket> = transactions
+            .into_iter()
+            .map(|products| Basket { products })
+            .collect();
+        let result = FPGrowth::from_source(baskets, 2).find_frequent_patterns();
+        assert_eq!(sorted_patterns(&result), expected);
+    }
+
+    #[test]
+    fn test_rules_with_min_lift() {
+        let transactions = small_fixture_transactions();
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        // lift(a => b) = (3 / 4) / (3 / 5), lift(a => c) = (2 / 4) / (3 / 5).
+        let rules: Vec<_> = result.rules_with_min_lift(1.0).collect();
+        assert!(rules
+            .iter()
+            .any(|rule| rule.antecedent() == ["a"] && rule.consequent() == ["b"]));
+        assert!(!rules
+            .iter()
+            .any(|rule| rule.antecedent() == ["a"] && rule.consequent() == ["c"]));
+        let all_rules = result.clone().into_rules(0.0);
+        assert!(rules.len() < all_rules.len());
+        assert!(rules.iter().all(|rule| all_rules.contains(rule)));
+        assert_eq!(result.rules_with_min_lift(0.0).count(), all_rules.len());
+        assert_eq!(
+            FPResult::from_patterns(vec![(vec!["a"], 2), (vec!["b"], 2), (vec!["a", "b"], 2)])
+                .rules_with_min_lift(0.0)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_elimination_sets_are_sorted() {
+        let transactions = fixture_transactions();
+        let test_cases: Vec<(usize, usize, usize)> = vec![
+            // (minimum_support, elimination_set_num, candidate_num)
+            (1, 88, 87),
+            (2, 47, 44),
+            (3, 20, 14),
+            (4, 20, 14),
+            (5, 17, 11),
+            (6, 15, 6),
+            (7, 14, 5),
+            (8, 14, 5),
+            (9, 10, 0),
+        ];
+        for (minimum_support, elimination_set_num, candidate_num) in test_cases.iter() {
+            let result = FPGrowth::<&str>::new(transactions.clone(), *minimum_support)
+                .find_frequent_patterns();
+            assert_eq!(result.elimination_sets_num(), *elimination_set_num);
+            // Besides the eliminated transactions, every elimination set is a sorted candidate.
+            let candidates: Vec<Vec<&str>> = result
+                .elimination_sets_sorted()
+                .into_iter()
+                .filter(|set| !transactions.contains(set))
+                .collect();
+            assert_eq!(candidates.len(), *candidate_num);
+            assert!(candidates
+                .iter()
+                .all(|set| set.windows(2).all(|items| items[0] <= items[1])));
+        }
+
+        let result = FPGrowth::<&str>::new(transactions.clone(), 7).find_frequent_patterns();
+        let candidates: Vec<Vec<&str>> = result
+            .elimination_sets_sorted()
+            .into_iter()
+            .filter(|set| !transactions.contains(set))
+            .collect();
+        assert_eq!(
+            candidates,
+            vec![
+                vec!["a", "a"],
+                vec!["a", "e"],
+                vec!["c", "c"],
+                vec!["c", "e"],
+                vec!["a", "a", "c"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frequent_subsets_of() {
+        let transactions = small_fixture_transactions();
+        let result = FPGrowth::<&str>::new(transactions, 2)
+            .include_singletons(true)
+            .find_frequent_patterns();
+        let mut subsets = result.frequent_subsets_of(&["b", "a", "e", "a"]);
+        subsets.sort();
+        assert_eq!(
+            subsets,
+            vec![(vec!["a"], 4), (vec!["a", "b"], 3), (vec!["b"], 3)]
+        );
+        assert!(result.frequent_subsets_of(&["e"]).is_empty());
+        assert_eq!(
+            result.frequent_subsets_of(&["a", "b", "c", "d"]).len(),
+            result.frequent_patterns_num()
+        );
+    }
+
+    #[test]
+    fn test_find_frequent_patterns_normalized() {
+        let transactions = vec![
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "b"],
+            vec!["a", "d"],
+        ];
+        let fp_growth_str = FPGrowth::<&str>::new(transactions, 2).include_singletons(true);
+        let mut patterns = fp_growth_str.find_frequent_patterns_normalized();
+        patterns.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            patterns,
+            vec![
+                (vec!["a"], 1.0),
+                (vec!["a", "b"], 0.75),
+                (vec!["a", "b", "c"], 0.5),
+                (vec!["a", "c"], 0.5),
+                (vec!["b"], 0.75),
+                (vec!["b", "c"], 0.5),
+                (vec!["c"], 0.5),
+            ]
+        );
+        assert!(FPGrowth::<&str>::new(vec![], 1)
+            .find_frequent_patterns_normalized()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_support_predicate() {
+        let transactions = fixture_transactions();
+        for minimum_support in 1..=9 {
+            let expected = FPGrowth::<&str>::new(transactions.clone(), minimum_support)
+                .find_frequent_patterns();
+            let result = FPGrowth::<&str>::new(transactions.clone(), minimum_support)
+                .with_support_predicate(move |support, _| support >= minimum_support)
+                .find_frequent_patterns();
+            let mut patterns = result.frequent_patterns();
+            let mut expected_patterns = expected.frequent_patterns();
+            patterns.sort();
+            expected_patterns.sort();
+            assert_eq!(patterns, expected_patterns);
+            assert_eq!(
+                result.elimination_sets_num(),
+                expected.elimination_sets_num()
+            );
+        }
+
+        // The longer patterns need a higher support to be frequent.
+        let result = FPGrowth::<&str>::new(transactions, 2)
+            .with_support_predicate(|support, pattern_len| support >= 2 * pattern_len)
+            .find_frequent_patterns();
+        assert!(result
+            .frequent_patterns()
+            .iter()
+            .all(|(pattern, support)| *support >= 2 * pattern.len()));
+        assert_eq!(result.support_of(&["a", "b"]), None);
+        assert_eq!(result.support_of(&["a", "c", "e"]), Some(6));
+    }
+
+    #[test]
+    fn test_node_pool() {
+        let mut tree = Tree::<&str>::new();
+        tree.add_transaction(vec!["a", "b", "c"]);
+        tree.add_transaction(vec!["a", "b", "d"]);
+        tree.add_transaction(vec!["a", "d"]);
+        let paths = tree.generate_prefix_path("d");
+        let mut node_pool = NodePool::new();
+        let partial_tree = Tree::generate_partial_tree_in(&paths, &mut node_pool);
+        assert_eq!(partial_tree.node_count(), 4);
+        partial_tree.recycle(&mut node_pool);
+        assert_eq!(node_pool.len(), 4);
+        // The recycled nodes are reused by the next partial tree.
+        let partial_tree = Tree::generate_partial_tree_in(&paths, &mut node_pool);
+        assert!(node_pool.is_empty());
+        assert_eq!(partial_tree.itemset_support(&["a", "b"]), 1);
+        assert_eq!(partial_tree.itemset_support(&["a"]), 2);
+        // The nodes referenced outside the tree are not recycled, neither are the ones still
+        // linked to them, i.e. the parent "a", the child "d" and its neighbor "d" of "b".
+        let b_nodes = partial_tree.get_all_nodes("b");
+        partial_tree.recycle(&mut node_pool);
+        assert!(node_pool.is_empty());
+        assert_eq!(b_nodes[0].count(), 1);
+        assert!(!b_nodes[0].is_leaf());
+
+        let transactions = fixture_transactions();
+        for minimum_support in 1..=9 {
+            let mut expected = FPGrowth::<&str>::new(transactions.clone(), minimum_support)
+                .find_frequent_patterns()
+                .frequent_patterns();
+            let fp_growth_str =
+                FPGrowth::<&str>::new(transactions.clone(), minimum_support).with_node_pool();
+            // The pool is reused by the second run.
+            for _ in 0..2 {
+                let mut patterns = fp_growth_str.find_frequent_patterns().frequent_patterns();
+                patterns.sort();
+                expected.sort();
+                assert_eq!(patterns, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_isolated_items() {
+        let transactions = vec![
+            vec!["a", "b", "e"],
+            vec!["a", "b", "d"],
+            vec!["c", "d"],
+            vec!["c", "e"],
+            vec!["d", "f"],
+            vec!["c"],
+        ];
+        let result = FPGrowth::<&str>::new(transactions.clone(), 2).find_frequent_patterns();
+        assert_eq!(result.isolated_items(), vec![("c", 3), ("d", 3), ("e", 2)]);
+
+        let result = FPGrowth::<&str>::new(transactions, 2)
+            .include_singletons(false)
+            .find_frequent_patterns();
+        assert!(result.isolated_items().is_empty());
+    }
+
+    #[test]
+    fn test_max_results() {
+        let transactions = fixture_transactions();
+        let all_patterns = FPGrowth::<&str>::new(transactions.clone(), 2)
+            .find_frequent_patterns()
+            .frequent_patterns();
+        assert_eq!(all_patterns.len(), 43);
+
+        for max_results in [0, 1, 10, 42] {
+            let result = FPGrowth::<&str>::new(transactions.clone(), 2)
+                .with_max_results(max_results)
+                .find_frequent_patterns();
+            assert!(!result.is_complete());
+            assert_eq!(result.frequent_patterns_num(), max_results);
+            assert!(result
+                .frequent_patterns()
+                .iter()
+                .all(|pattern| all_patterns.contains(pattern)));
+        }
+        for max_results in [43, 100] {
+            let result = FPGrowth::<&str>::new(transactions.clone(), 2)
+                .with_max_results(max_results)
+                .try_find_frequent_patterns()
+                .unwrap();
+            assert!(result.is_complete());
+            assert_eq!(result.frequent_patterns_num(), 43);
+        }
+
+        let (tx, rx) = channel();
+        FPGrowth::<&str>::new(transactions, 2)
+            .with_max_results(5)
+            .mine_to_channel(tx);
+        assert_eq!(rx.iter().count(), 5);
+    }
+
+    #[test]
+    fn test_item_hierarchy() {
+        let transactions = vec![
+            vec!["skim milk"],
+            vec!["2% milk"],
+            vec!["2% milk", "bread"],
+            vec!["skim milk", "bread"],
+            vec!["cheddar"],
+        ];
+        let parents: HashMap<&str, &str> = vec![
+            ("skim milk", "milk"),
+            ("2% milk", "milk"),
+            ("milk", "dairy"),
+            ("cheddar", "cheese"),
+            ("cheese", "dairy"),
+        ]
+        .into_iter()
+        .collect();
+        let result = FPGrowth::<&str>::new(transactions, 2)
+            .with_item_hierarchy(parents)
+            .unwrap()
+            .find_frequent_patterns();
+        let mut patterns = result.frequent_patterns();
+        patterns.sort();
+        assert_eq!(
+            patterns,
+            vec![
+                (vec!["2% milk"], 2),
+                (vec!["bread"], 2),
+                (vec!["bread", "dairy"], 2),
+                (vec!["bread", "milk"], 2),
+                (vec!["dairy"], 5),
+                (vec!["milk"], 4),
+                (vec!["skim milk"], 2),
+            ]
+        );
+
+        let cyclic_parents: HashMap<&str, &str> = vec![("a", "b"), ("b", "c"), ("c", "b")]
+            .into_iter()
+            .collect();
+        assert!(matches!(
+            FPGrowth::<&str>::new(vec![], 1).with_item_hierarchy(cyclic_parents),
+            Err(Error::CyclicHierarchy { .. })
+        ));
+        let self_parents: HashMap<&str, &str> = vec![("a", "a")].into_iter().collect();
+        assert_eq!(
+            FPGrowth::<&str>::new(vec![], 1)
+                .with_item_hierarchy(self_parents)
+                .err(),
+            Some(Error::CyclicHierarchy {
+                item: "\"a\"".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_cooccurrence_matrix() {
+        let transactions = vec![
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c", "a"],
+            vec!["a", "b"],
+            vec!["a", "d"],
+            vec!["c", "e"],
+        ];
+        let (items, matrix) = FPGrowth::<&str>::new(transactions, 2).cooccurrence_matrix();
+        assert_eq!(items, vec!["a", "b", "c"]);
+        assert_eq!(matrix, vec![vec![4, 3, 2], vec![3, 3, 2], vec![2, 2, 3]]);
+
+        let (items, matrix) = FPGrowth::<&str>::new(vec![], 1).cooccurrence_matrix();
+        assert!(items.is_empty());
+        assert!(matrix.is_empty());
+    }
+
+    #[test]
+    fn test_elimination_observer() {
+        let transactions = fixture_transactions();
+        for minimum_support in 1..=9 {
+            let fp_growth_str = FPGrowth::<&str>::new(transactions.clone(), minimum_support);
+            let expected = fp_growth_str.find_frequent_patterns();
+            let mut observed_sets = HashSet::new();
+            let result = fp_growth_str.find_frequent_patterns_with_elimination_observer(|set| {
+                observed_sets.insert(set.to_vec());
+            });
+            assert_eq!(result.elimination_sets_num(), 0);
+            assert_eq!(
+                result.frequent_patterns_num(),
+                expected.frequent_patterns_num()
+            );
+            let expected_sets: HashSet<Vec<&str>> =
+                expected.elimination_sets().into_iter().collect();
+            assert_eq!(observed_sets, expected_sets);
+        }
+    }
+
+    #[test]
+    fn test_compact_tree() {
+        let transactions = vec![
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["a", "c", "e", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+        ];
+        let mut tree = Tree::<&str>::new();
+        let mut compact_tree = CompactTree::<&str>::new();
+        for transaction in transactions.into_iter() {
+            tree.add_transaction(transaction.clone());
+            compact_tree.add_transaction(transaction);
+        }
+        assert_eq!(compact_tree.node_count(), tree.node_count());
+        let mut item_supports = compact_tree.item_supports();
+        item_supports.sort();
+        assert_eq!(
+            item_supports,
+            vec![
+                ("a", 6),
+                ("b", 2),
+                ("c", 6),
+                ("d", 2),
+                ("e", 6),
+                ("f", 2),
+                ("g", 3)
+            ]
+        );
+        let partial_tree = compact_tree.generate_partial_tree("g");
+        let expected_partial_tree = Tree::generate_partial_tree(&tree.generate_prefix_path("g"));
+        assert_eq!(
+            partial_tree.node_count(),
+            expected_partial_tree.node_count()
+        );
+        assert_eq!(partial_tree.item_support("a"), 3);
+        assert_eq!(partial_tree.item_support("e"), 2);
+        assert_eq!(partial_tree.item_support("b"), 0);
+
+        for seed in 0..4 {
+            let transactions: Vec<Vec<u64>> = random_transactions(300, 30, 6, seed)
+                .into_iter()
+                .map(|transaction| {
+                    transaction
+                        .into_iter()
+                        .map(|item| u64::from(item) << 40)
+                        .collect()
+                })
+                .collect();
+            let expected = FPGrowth::<u64>::new(transactions.clone(), 10).find_frequent_patterns();
+            let result = FPGrowth::<u64>::new(transactions.clone(), 10)
+                .with_compact_tree()
+                .find_frequent_patterns();
+            let mut patterns = result.frequent_patterns();
+            let mut expected_patterns = expected.frequent_patterns();
+            patterns.sort();
+            expected_patterns.sort();
+            assert_eq!(patterns, expected_patterns);
+            assert_eq!(
+                result.elimination_sets_sorted(),
+                expected.elimination_sets_sorted()
+            );
+            assert_eq!(
+                FPGrowth::<u64>::new(transactions, 10)
+                    .with_compact_tree()
+                    .with_memory_budget(1)
+                    .try_find_frequent_patterns()
+                    .err(),
+                Some(Error::MemoryBudgetExceeded { max_nodes: 1 })
+            );
+        }
+    }
+
+    #[test]
+    fn test_fully_retained_fraction() {
+        let transactions = vec![
+            vec!["a", "b"],
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["b"],
+        ];
+        let fraction = |minimum_support| {
+            FPGrowth::<&str>::new(transactions.clone(), minimum_support).fully_retained_fraction()
+        };
+        assert_eq!(fraction(1), 1.0);
+        assert_eq!(fraction(2), 0.5);
+        assert_eq!(fraction(3), 0.5);
+        assert_eq!(fraction(4), 0.0);
+        assert_eq!(
+            FPGrowth::<&str>::new(vec![], 1).fully_retained_fraction(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_prefix_path_items() {
+        let mut tree = Tree::<&str>::new();
+        tree.add_transaction(vec!["a", "b", "c"]);
+        tree.add_transaction(vec!["a", "b", "c"]);
+        tree.add_transaction(vec!["a", "c"]);
+        tree.add_transaction(vec!["b", "c"]);
+        for item in ["a", "b", "c", "d"] {
+            let paths = tree.generate_prefix_path(item);
+            let path_items = tree.generate_prefix_path_items(item);
+            let expected_path_items: Vec<(Vec<&str>, usize)> = paths
+                .iter()
+                .map(|path| {
+                    (
+                        path.iter().map(|node| node.item().unwrap()).collect(),
+                        path.last().unwrap().count(),
+                    )
+                })
+                .collect();
+            assert_eq!(path_items, expected_path_items);
+
+            let partial_tree = Tree::generate_partial_tree_from_items(&path_items);
+            let expected_partial_tree = Tree::generate_partial_tree(&paths);
+            assert_eq!(
+                partial_tree.reconstruct_transactions(),
+                expected_partial_tree.reconstruct_transactions()
+            );
+        }
+        let mut path_items = tree.generate_prefix_path_items("c");
+        path_items.sort();
+        assert_eq!(
+            path_items,
+            vec![
+                (vec!["a", "b", "c"], 2),
+                (vec!["a", "c"], 1),
+                (vec!["b", "c"], 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_patterns_in_support_range() {
+        let transactions = fixture_transactions();
+        let fp_growth_str = FPGrowth::<&str>::new(transactions.clone(), 1);
+        let all_patterns = fp_growth_str.find_frequent_patterns().frequent_patterns();
+        for (lo, hi) in [(2, 5), (3, 3), (1, 100), (6, 8), (5, 2)] {
+            let mut patterns = fp_growth_str
+                .find_patterns_in_support_range(lo, hi)
+                .frequent_patterns();
+            let mut expected_patterns: Vec<(Vec<&str>, usize)> = all_patterns
+                .iter()
+                .filter(|(_, support)| lo <= *support && *support <= hi)
+                .cloned()
+                .collect();
+            patterns.sort();
+            expected_patterns.sort();
+            assert_eq!(patterns, expected_patterns);
+        }
+        // The supersets of a pattern above the range could be in the range.
+        let patterns = fp_growth_str
+            .find_patterns_in_support_range(2, 6)
+            .frequent_patterns();
+        assert!(!patterns
+            .iter()
+            .any(|(pattern, _)| pattern == &vec!["a", "c"]));
+        assert!(patterns
+            .iter()
+            .any(|(pattern, _)| pattern == &vec!["a", "c", "e"]));
+    }
+
+    #[test]
+    fn test_page() {
+        let result = FPResult::from_patterns(vec![
+            (vec!["b"], 3),
+            (vec!["c", "a"], 2),
+            (vec!["a"], 3),
+            (vec!["a", "b", "c"], 1),
+            (vec!["c"], 2),
+        ]);
+        assert_eq!(
+            result.page(SortKey::Support, 0, 3),
+            vec![(vec!["a"], 3), (vec!["b"], 3), (vec!["a", "c"], 2)]
+        );
+        assert_eq!(
+            result.page(SortKey::Support, 3, 3),
+            vec![(vec!["c"], 2), (vec!["a", "b", "c"], 1)]
+        );
+        assert!(result.page(SortKey::Support, 5, 3).is_empty());
+        assert_eq!(
+            result.page(SortKey::Length, 0, 2),
+            vec![(vec!["a", "b", "c"], 1), (vec!["a", "c"], 2)]
+        );
+        assert_eq!(
+            result.page(SortKey::Lexicographic, 1, 2),
+            vec![(vec!["a", "b", "c"], 1), (vec!["a", "c"], 2)]
+        );
+    }
+
+    #[test]
+    fn test_null_invariant_measures() {
+        let result = FPResult::from_patterns(vec![
+            (vec!["a"], 4),
+            (vec!["b"], 9),
+            (vec!["c"], 6),
+            (vec!["a", "b"], 3),
+            (vec!["a", "b", "c"], 2),
+        ]);
+        let close_to = |value: Option<f64>, expected: f64| (value.unwrap() - expected).abs() < 1e-9;
+        // 0.5 * (3 / 4 + 3 / 9)
+        assert!(close_to(
+            result.interest_of(&["a", "b"], InterestMeasure::Kulczynski),
+            0.5 * (0.75 + 1.0 / 3.0)
+        ));
+        // 3 / sqrt(4 * 9)
+        assert!(close_to(
+            result.interest_of(&["a", "b"], InterestMeasure::Cosine),
+            0.5
+        ));
+        assert!(close_to(
+            result.interest_of(&["a", "b", "c"], InterestMeasure::Kulczynski),
+            (2.0 / 4.0 + 2.0 / 9.0 + 2.0 / 6.0) / 3.0
+        ));
+        // 2 / cbrt(4 * 9 * 6)
+        assert!(close_to(
+            result.interest_of(&["a", "b", "c"], InterestMeasure::Cosine),
+            2.0 / 216f64.cbrt()
+        ));
+        // Both are 1.0 for a single item and don't need the number of transactions.
+        assert!(close_to(
+            result.interest_of(&["b"], InterestMeasure::Cosine),
+            1.0
+        ));
+        assert_eq!(
+            result.interest_of(&["a", "c"], InterestMeasure::Cosine),
+            None
+        );
+    }
+
+    #[test]
+    fn test_merge_trees() {
+        let transactions = random_transactions(200, 20, 5, 7);
+        let build = |transactions: &[Vec<u32>]| {
+            let mut tree = Tree::<u32>::new();
+            for transaction in transactions.iter() {
+                let mut transaction = transaction.clone();
+                transaction.sort_unstable();
+                transaction.dedup();
+                tree.add_transaction(transaction);
+            }
+            tree
+        };
+        let expected_tree = build(&transactions);
+        let (left, right) = transactions.split_at(120);
+        let mut tree = build(left);
+        tree.merge(build(right));
+        assert_eq!(tree.node_count(), expected_tree.node_count());
+        assert_eq!(tree.header_summary(), expected_tree.header_summary());
+        let mut reconstructed = tree.reconstruct_transactions();
+        let mut expected_reconstructed = expected_tree.reconstruct_transactions();
+        reconstructed.sort();
+        expected_reconstructed.sort();
+        assert_eq!(reconstructed, expected_reconstructed);
+        let mut patterns = FPGrowth::mine_existing_tree(&tree, 20).frequent_patterns();
+        let mut expected_patterns =
+            FPGrowth::mine_existing_tree(&expected_tree, 20).frequent_patterns();
+        patterns.sort();
+        expected_patterns.sort();
+        assert_eq!(patterns, expected_patterns);
+
+        let mut tree = Tree::<u32>::new();
+        tree.merge(build(&transactions));
+        assert_eq!(tree.header_summary(), expected_tree.header_summary());
+    }
+
+    #[test]
+    fn test_estimate_pattern_count() {
+        let transactions = fixture_transactions();
+        for minimum_support in 1..=9 {
+            let fp_growth_str = FPGrowth::<&str>::new(transactions.clone(), minimum_support);
+            let estimate = fp_growth_str.estimate_pattern_count();
+            assert!(
+                estimate
+                    >= fp_growth_str
+                        .find_frequent_patterns()
+                        .frequent_patterns_num()
+            );
+            let fp_growth_str = fp_growth_str.with_max_depth(2);
+            assert!(
+                fp_growth_str.estimate_pattern_count()
+                    >= fp_growth_str
+                        .find_frequent_patterns()
+                        .frequent_patterns_num()
+            );
+            assert!(fp_growth_str.estimate_pattern_count() <= estimate);
+        }
+        // The only leaf paths are "a c e" and "b", i.e. 7 + 1 subsets.
+        let fp_growth_str = FPGrowth::<&str>::new(
+            vec![
+                vec!["a", "c", "e"],
+                vec!["a", "c", "e"],
+                vec!["b"],
+                vec!["b"],
+            ],
+            2,
+        );
+        assert_eq!(fp_growth_str.estimate_pattern_count(), 8);
+        assert_eq!(FPGrowth::<&str>::new(vec![], 1).estimate_pattern_count(), 0);
+
+        // A single long transaction saturates the estimate.
+        let long_transaction: Vec<u32> = (0..100).collect();
+        assert_eq!(
+            FPGrowth::<u32>::new(vec![long_transaction], 1).estimate_pattern_count(),
+            usize::MAX
+        );
+    }
+
+    #[test]
+    fn test_top_k_rules_by_lift() {
+        let mut transactions = small_fixture_transactions();
+        transactions.push(vec!["b", "c", "d"]);
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        let lift_of = |rule: &AssociationRule<&'static str>| {
+            let consequent_support = result.support_of(rule.consequent()).unwrap() as f64;
+            rule.confidence() * 6.0 / consequent_support
+        };
+        let mut expected: Vec<AssociationRule<&'static str>> = result
+            .rules_with_min_lift(f64::NEG_INFINITY)
+            .filter(|rule| rule.confidence() >= 0.5)
+            .collect();
+        expected.sort_by(|a, b| {
+            lift_of(b)
+                .total_cmp(&lift_of(a))
+                .then_with(|| a.antecedent().cmp(b.antecedent()))
+                .then_with(|| a.consequent().cmp(b.consequent()))
+        });
+        assert!(expected.len() > 3);
+        for k in 0..=expected.len() + 1 {
+            let top_rules = result.top_k_rules_by_lift(k, 0.5);
+            assert_eq!(top_rules, expected[..k.min(expected.len())]);
+        }
+        assert!(FPResult::from_patterns(vec![(vec!["a", "b"], 2)])
+            .top_k_rules_by_lift(3, 0.0)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_closed_patterns_within() {
+        let transactions = vec![
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "b"],
+            vec!["a", "d"],
+            vec!["a", "d"],
+            vec!["a"],
+        ];
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        assert_eq!(result.closed_patterns_within(0), result.closed_patterns());
+        // support(a, b) = 4 is within 1 of support(a, b, c) = 3, but support(a) = 7 is not.
+        assert_eq!(
+            result.closed_patterns_within(1),
+            vec![
+                (vec!["a"], 7),
+                (vec!["a", "b", "c"], 3),
+                (vec!["a", "d"], 2)
+            ]
+        );
+        assert_eq!(
+            result.closed_patterns_within(usize::MAX),
+            vec![(vec!["a", "b", "c"], 3), (vec!["a", "d"], 2)]
+        );
+    }
+
+    #[cfg(feature = "threadsafe")]
+    #[test]
+    fn test_threadsafe_tree() {
+        fn assert_send_sync<S: Send + Sync>(_: &S) {}
+        let mut tree = Tree::<u32>::new();
+        tree.add_transaction(vec![1, 2, 3]);
+        tree.add_transaction(vec![1, 2]);
+        assert_send_sync(&tree);
+        let tree = std::sync::Arc::new(tree);
+        let handles: Vec<_> = (1..=3)
+            .map(|item| {
+                let tree = std::sync::Arc::clone(&tree);
+                std::thread::spawn(move || tree.generate_prefix_path(item).len())
+            })
+            .collect();
+        let path_nums: Vec<usize> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(path_nums, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_find_pairs() {
+        let transactions = vec![
+            vec!["e", "c", "a", "b", "f", "h"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["e", "c", "a", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e", "e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+            vec!["g", "c", "e", "a"],
+            vec!["a", "c", "e", "g"],
+            vec!["i"],
+        ];
+        for minimum_support in 1..=9 {
+            let fp_growth = FPGrowth::<&str>::new(transactions.clone(), minimum_support);
+            let mut pairs = fp_growth.find_pairs().frequent_patterns();
+            let mut expected = FPGrowth::<&str>::new(transactions.clone(), minimum_support)
+                .with_max_depth(2)
+                .find_frequent_patterns()
+                .frequent_patterns();
+            pairs.sort();
+            expected.sort();
+            assert_eq!(pairs, expected);
+        }
+        let fp_growth = FPGrowth::<&str>::new(transactions, 2)
+            .include_singletons(false)
+            .with_max_results(3);
+        let result = fp_growth.find_pairs();
+        assert_eq!(result.frequent_patterns_num(), 3);
+        assert!(!result.is_complete());
+        assert!(result.frequent_patterns().iter().all(|(p, _)| p.len() == 2));
+
+        // The other bucket is only counted by the tree, and no tree is built for the budget.
+        let transactions = vec![
+            vec!["a", "b", "x"],
+            vec!["a", "y"],
+            vec!["a", "b"],
+            vec!["a", "z"],
+        ];
+        let fp_growth = FPGrowth::new(transactions, 2)
+            .with_other_bucket("OTHER")
+            .with_memory_budget(1)
+            .with_max_depth(2);
+        assert_eq!(
+            sorted_patterns(&fp_growth.find_pairs()),
+            vec![(vec!["a"], 4), (vec!["a", "b"], 2), (vec!["b"], 2)]
+        );
+        // "a" and the other bucket are as frequent, so both are dropped to fit into one node.
+        assert_eq!(
+            fp_growth.find_frequent_patterns().frequent_patterns_num(),
+            0
+        );
+        let fp_growth = fp_growth.with_memory_budget(usize::MAX);
+        assert_eq!(
+            sorted_patterns(&fp_growth.find_frequent_patterns()),
+            vec![
+                (vec!["OTHER"], 3),
+                (vec!["OTHER", "a"], 3),
+                (vec!["a"], 4),
+                (vec!["a", "b"], 2),
+                (vec!["b"], 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_input_report() {
+        let transactions = vec![
+            vec!["a", "b", "c"],
+            vec![],
+            vec!["c", "b", "a", "a"],
+            vec!["a", "d"],
+            vec!["a", "b", "c"],
+            vec![],
+        ];
+        let report = FPGrowth::<&str>::new(transactions, 2).input_report();
+        assert_eq!(report.transactions_num(), 6);
+        assert_eq!(report.empty_count(), 2);
+        assert_eq!(report.duplicate_count(), 3);
+        assert_eq!(report.min_len(), 0);
+        assert_eq!(report.max_len(), 3);
+        assert_eq!(report.avg_len(), 11.0 / 6.0);
+        assert_eq!(report.singleton_items(), ["d"]);
+
+        let report = FPGrowth::<&str>::new(vec![], 2).input_report();
+        assert_eq!(report.transactions_num(), 0);
+        assert_eq!(report.min_len(), 0);
+        assert_eq!(report.avg_len(), 0.0);
+    }
+
+    #[test]
+    fn test_from_aggregated() {
+        let aggregated = vec![
+            (vec!["a", "c", "e", "g"], 3),
+            (vec!["a", "c", "d"], 2),
+            (vec!["e"], 2),
+            (vec!["a", "c", "e", "b", "f", "b"], 1),
+            (vec!["i"], 0),
+        ];
+        let expanded: Vec<Vec<&str>> = aggregated
+            .iter()
+            .flat_map(|(transaction, count)| vec![transaction.clone(); *count])
+            .collect();
+        for minimum_support in 1..=5 {
+            let fp_growth = FPGrowth::from_aggregated(aggregated.clone(), minimum_support);
+            let expected = FPGrowth::<&str>::new(expanded.clone(), minimum_support);
+            let mut patterns = fp_growth.find_frequent_patterns().frequent_patterns();
+            let mut expected_patterns = expected.find_frequent_patterns().frequent_patterns();
+            patterns.sort();
+            expected_patterns.sort();
+            assert_eq!(patterns, expected_patterns);
+            assert_eq!(
+                fp_growth.find_frequent_patterns().elimination_sets_sorted(),
+                expected.find_frequent_patterns().elimination_sets_sorted()
+            );
+            assert_eq!(
+                fp_growth.find_frequent_patterns().transactions_num(),
+                expanded.len()
+            );
+            // The items are counted from the normalized aggregated transactions as well.
+            let normalized = FPGrowth::from_aggregated(aggregated.clone(), minimum_support)
+                .with_item_normalizer(|item| item);
+            assert_eq!(
+                normalized.find_frequent_patterns().frequent_patterns_num(),
+                expected_patterns.len()
+            );
+        }
+
+        // A transaction occurring 0 times eliminates nothing.
+        let fp_growth =
+            FPGrowth::from_aggregated(vec![(vec!["a", "b"], 0), (vec!["a"], 3), (vec!["b"], 3)], 1);
+        let expected = FPGrowth::new(
+            vec![
+                vec!["a"],
+                vec!["a"],
+                vec!["a"],
+                vec!["b"],
+                vec!["b"],
+                vec!["b"],
+            ],
+            1,
+        )
+        .find_frequent_patterns();
+        let result = fp_growth.find_frequent_patterns();
+        assert_eq!(sorted_patterns(&result), sorted_patterns(&expected));
+        assert_eq!(
+            result.elimination_sets_sorted(),
+            expected.elimination_sets_sorted()
+        );
+        assert!(!result.elimination_sets().contains(&vec!["a", "b"]));
+    }
+
+    #[test]
+    fn test_tree_decay() {
+        let mut tree = Tree::<&str>::new();
+        for _ in 0..4 {
+            tree.add_transaction(vec!["a", "b", "c"]);
+        }
+        tree.add_transaction(vec!["a", "b", "d"]);
+        tree.add_transaction(vec!["a", "e"]);
+        tree.add_transaction(vec!["e", "c"]);
+        tree.add_transaction(vec!["e", "c"]);
+        assert_eq!(tree.node_count(), 7);
+
+        tree.decay(0.5);
+        // a: 6 -> 3, b: 5 -> 2, c: 4 -> 2, e: 2 -> 1, c: 2 -> 1, and d, e under a are pruned.
+        assert_eq!(tree.node_count(), 5);
+        let mut transactions = tree.reconstruct_transactions();
+        transactions.sort();
+        assert_eq!(
+            transactions,
+            vec![
+                (vec!["a"], 1),
+                (vec!["a", "b", "c"], 2),
+                (vec!["e", "c"], 1)
+            ]
+        );
+        assert_eq!(tree.get_all_nodes("c").len(), 2);
+        assert_eq!(tree.itemset_support(&["c"]), 3);
+        assert!(tree.get_all_nodes("d").is_empty());
+        assert_eq!(tree.get_all_nodes("e").len(), 1);
+
+        tree.decay(0.1);
+        assert_eq!(tree.node_count(), 0);
+        assert!(tree.get_all_nodes("a").is_empty());
+        assert!(tree.reconstruct_transactions().is_empty());
+    }
+
+    #[test]
+    fn test_tree_decay_factor() {
+        let mut tree = Tree::<&str>::new();
+        tree.add_transaction(vec!["a", "b"]);
+        tree.decay(1.0);
+        assert_eq!(tree.itemset_support(&["a", "b"]), 1);
+        for factor in [-0.5, 1.5, f64::NAN, f64::INFINITY].iter().copied() {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                tree.decay(factor);
+            }));
+            assert!(result.is_err());
+        }
+        // The tree is left untouched by an invalid factor.
+        assert_eq!(tree.itemset_support(&["a", "b"]), 1);
+        tree.decay(0.0);
+        assert_eq!(tree.node_count(), 0);
+    }
+
+    #[test]
+    fn test_tree_root() {
+        let mut tree = Tree::<&str>::new();
+        tree.add_transaction(vec!["a", "b"]);
+        tree.add_transaction(vec!["a", "c"]);
+        tree.add_transaction(vec!["d"]);
+        let root_node = tree.root();
+        assert!(root_node.is_root());
+        let children = root_node.children();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].item(), Some("a"));
+        assert_eq!(children[0].count(), 2);
+        assert!(children[0]
+            .parent()
+            .is_some_and(|node| Rc::ptr_eq(&node, &root_node)));
+
+        // Walk the whole tree from the root.
+        let mut items = vec![];
+        let mut pending_nodes = vec![root_node];
+        while let Some(node) = pending_nodes.pop() {
+            items.extend(node.item());
+            pending_nodes.extend(node.children());
+        }
+        items.sort();
+        assert_eq!(items, vec!["a", "b", "c", "d"]);
+        assert_eq!(items.len(), tree.node_count());
+    }
+
+    #[test]
+    fn test_prefix_span() {
+        let sequences = vec![
+            vec!["a", "b", "c"],
+            vec!["a", "c"],
+            vec!["b", "c", "a"],
+            vec!["a", "b", "a"],
+        ];
+        let prefix_span = PrefixSpan::new(sequences);
+        assert_eq!(
+            prefix_span.find_frequent_sequences(2),
+            vec![
+                (vec!["a"], 4),
+                (vec!["a", "b"], 2),
+                (vec!["a", "c"], 2),
+                (vec!["b"], 3),
+                (vec!["b", "a"], 2),
+                (vec!["b", "c"], 2),
+                (vec!["c"], 3),
+            ]
+        );
+        // The order matters, and an item could repeat in a sequence.
+        let frequent_sequences = prefix_span.find_frequent_sequences(1);
+        assert!(frequent_sequences.contains(&(vec!["a", "b", "a"], 1)));
+        assert!(frequent_sequences.contains(&(vec!["b", "c", "a"], 1)));
+        assert!(!frequent_sequences
+            .iter()
+            .any(|(sequence, _)| sequence == &["c", "b"]));
+        assert!(prefix_span.find_frequent_sequences(5).is_empty());
+        assert!(PrefixSpan::<u32>::new(vec![])
+            .find_frequent_sequences(0)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_minimal_rule_base() {
+        let transactions = vec![
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "b"],
+            vec!["a", "c"],
+            vec!["b"],
+        ];
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        let rules: Vec<(Vec<&str>, Vec<&str>, usize)> = result
+            .minimal_rule_base(0.7)
+            .iter()
+            .map(|rule| {
+                (
+                    rule.antecedent().to_vec(),
+                    rule.consequent().to_vec(),
+                    rule.support(),
+                )
+            })
+            .collect();
+        // c => a is exact, the closure of c is {a, c}. So is b, c => a.
+        assert_eq!(
+            rules,
+            vec![
+                (vec!["a"], vec!["b"], 3),
+                (vec!["a"], vec!["c"], 3),
+                (vec!["b"], vec!["a"], 3),
+                (vec!["b", "c"], vec!["a"], 2),
+                (vec!["c"], vec!["a"], 3),
+            ]
+        );
+        let all_rules = result.clone().into_rules(0.0);
+        let rule_base = result.minimal_rule_base(0.0);
+        assert_eq!(rule_base.len(), 9);
+        assert!(rule_base.len() < all_rules.len());
+        // Every rule could be derived from a rule of the base with the same support and
+        // confidence, whose antecedent is smaller and whose items are more.
+        for rule in all_rules.iter() {
+            assert!(rule_base.iter().any(|base_rule| {
+                base_rule.support() == rule.support()
+                    && base_rule.confidence() == rule.confidence()
+                    && base_rule
+                        .antecedent()
+                        .iter()
+                        .all(|item| rule.antecedent().contains(item))
+                    && rule
+                        .antecedent()
+                        .iter()
+                        .chain(rule.consequent().iter())
+                        .all(|item| {
+                            base_rule.antecedent().contains(item)
+                                || base_rule.consequent().contains(item)
+                        })
+            }));
+        }
+    }
+
+    #[test]
+    fn test_window_miner() {
+        let stream = vec![
+            vec!["e", "c", "a", "b", "f", "h"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["e", "c", "a", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e", "e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+            vec!["g", "c", "e", "a"],
+            vec!["a", "c", "e", "g"],
+            vec!["i"],
+        ];
+        let fp_growth = FPGrowth::<&str>::new(vec![], 2);
+        let results: Vec<FPResult<&str>> = fp_growth.window_miner(stream.clone(), 4, 3).collect();
+        // The windows end at the 4th, 7th, 10th and 11th transaction.
+        assert_eq!(results.len(), 4);
+        for (result, window_end) in results.iter().zip([4, 7, 10, 11]) {
+            let window = stream[window_end - 4..window_end].to_vec();
+            let mut expected = FPGrowth::<&str>::new(window, 2)
+                .find_frequent_patterns()
+                .frequent_patterns();
+            let mut patterns = result.frequent_patterns();
+            expected.sort();
+            patterns.sort();
+            assert_eq!(patterns, expected);
+            assert_eq!(result.transactions_num(), 4);
+        }
+        assert_eq!(fp_growth.window_miner(vec![], 4, 3).count(), 0);
+
+        let mut tree = Tree::<&str>::new();
+        tree.add_transaction(vec!["a", "b"]);
+        tree.add_transaction(vec!["a", "c"]);
+        assert!(!tree.remove_transaction(vec!["a", "d"]));
+        assert!(tree.remove_transaction(vec!["a", "b"]));
+        assert_eq!(tree.node_count(), 2);
+        assert!(tree.get_all_nodes("b").is_empty());
+        assert_eq!(tree.itemset_support(&["a"]), 1);
+
+        // A prefix of the transactions added is not a transaction added itself.
+        let mut tree = Tree::<&str>::new();
+        tree.add_transaction(vec!["a", "b"]);
+        assert!(!tree.remove_transaction(vec!["a"]));
+        assert_eq!(tree.node_count(), 2);
+        assert_eq!(tree.reconstruct_transactions(), vec![(vec!["a", "b"], 1)]);
+        tree.add_transaction(vec!["a"]);
+        assert!(tree.remove_transaction(vec!["a"]));
+        assert!(!tree.remove_transaction(vec!["a"]));
+        assert_eq!(tree.reconstruct_transactions(), vec![(vec!["a", "b"], 1)]);
+    }
+
+    #[test]
+    fn test_tree_nodes_are_dropped() {
+        // The items are Copy, so they can't count their drops. Every node is watched by a weak
+        // reference instead, which can't be upgraded once the node is deallocated.
+        fn watch_nodes(tree: &Tree<u32>) -> Vec<crate::sync::Weak<Node<u32>>> {
+            let mut watched_nodes = vec![];
+            let mut pending_nodes = vec![tree.root()];
+            while let Some(node) = pending_nodes.pop() {
+                watched_nodes.push(Rc::downgrade(&node));
+                pending_nodes.extend(node.children());
+            }
+            watched_nodes
+        }
+
+        let mut tree = Tree::<u32>::new();
+        for i in 0..50u32 {
+            tree.add_transaction((0..i % 7).map(|j| (i + j) % 5).collect());
+        }
+        let mut other_tree = Tree::<u32>::new();
+        other_tree.add_transaction(vec![1, 2, 3]);
+        tree.merge(other_tree);
+        let partial_tree = Tree::generate_partial_tree(&tree.generate_prefix_path(3));
+        let item_partial_tree =
+            Tree::generate_partial_tree_from_items(&tree.generate_prefix_path_items(2));
+        let mut decayed_tree = Tree::<u32>::new();
+        for i in 0..10u32 {
+            decayed_tree.add_transaction(vec![i % 3, 4]);
+        }
+        decayed_tree.decay(0.4);
+
+        let mut watched_nodes = vec![];
+        for tree in [&tree, &partial_tree, &item_partial_tree, &decayed_tree] {
+            watched_nodes.extend(watch_nodes(tree));
+        }
+        assert!(watched_nodes.len() > tree.node_count());
+        drop((tree, partial_tree, item_partial_tree, decayed_tree));
+        assert!(watched_nodes.iter().all(|node| node.upgrade().is_none()));
+    }
+
+    #[test]
+    fn test_transactions_containing() {
+        let transactions: Vec<Vec<u32>> = (0..200u32)
+            .map(|i| (0..8).filter(|item| (i * 7 + item * 3) % 5 < 2).collect())
+            .collect();
+        let fp_growth = FPGrowth::new(transactions.clone(), 10);
+        let itemsets: Vec<Vec<u32>> = vec![vec![], vec![0], vec![1, 3], vec![2, 2, 7], vec![9]];
+        for _ in 0..2 {
+            for itemset in itemsets.iter() {
+                let expected = transactions
+                    .iter()
+                    .filter(|transaction| itemset.iter().all(|item| transaction.contains(item)))
+                    .count();
+                assert_eq!(fp_growth.transactions_containing(itemset), expected);
+            }
+        }
+        let result = fp_growth.find_frequent_patterns();
+        for (pattern, support) in result.frequent_patterns().iter() {
+            assert_eq!(fp_growth.transactions_containing(pattern), *support);
+        }
+        // The index is rebuilt with the normalized items.
+        let fp_growth = fp_growth.with_item_normalizer(|item| item / 2);
+        assert_eq!(
+            fp_growth.transactions_containing(&[0]),
+            transactions
+                .iter()
+                .filter(|transaction| transaction.iter().any(|&item| item < 2))
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_transactions_containing_undercounted() {
+        // A source whose number of transactions is lower than the transactions it yields.
+        struct UndercountedTransactions(Vec<Vec<u32>>);
+
+        impl TransactionSource<u32> for UndercountedTransactions {
+            fn transactions(&self) -> Box<dyn Iterator<Item = Cow<'_, [u32]>> + '_> {
+                Box::new(
+                    self.0
+                        .iter()
+                        .map(|transaction| Cow::Borrowed(transaction.as_slice())),
+                )
+            }
+
+            fn transactions_num(&self) -> usize {
+                1
+            }
+        }
+
+        let transactions: Vec<Vec<u32>> = (0..150u32).map(|i| vec![i % 3, 3]).collect();
+        let fp_growth =
+            FPGrowth::from_source(UndercountedTransactions(transactions.clone()), 1usize);
+        assert_eq!(fp_growth.transactions_containing(&[3]), 150);
+        assert_eq!(fp_growth.transactions_containing(&[0, 3]), 50);
+        assert_eq!(fp_growth.transactions_containing(&[0, 1]), 0);
+    }
+
+    #[test]
+    fn test_support_histogram() {
+        let result = FPResult::from_patterns(vec![
+            (vec!["a"], 10),
+            (vec!["b"], 9),
+            (vec!["c"], 5),
+            (vec!["a", "b"], 3),
+            (vec!["a", "c"], 2),
+            (vec!["b", "c"], 2),
+        ]);
+        // The supports range from 2 to 10, i.e. 3 bins of the width 3.
+        assert_eq!(result.support_histogram(3), vec![(4, 3), (7, 1), (10, 2)]);
+        assert_eq!(result.support_histogram(4), vec![(4, 3), (7, 1), (10, 2)]);
+        assert_eq!(result.support_histogram(1), vec![(10, 6)]);
+        let histogram = result.support_histogram(100);
+        assert_eq!(histogram.len(), 9);
+        assert_eq!(histogram[0], (2, 2));
+        assert_eq!(histogram[1], (3, 1));
+        assert_eq!(histogram.iter().map(|(_, count)| count).sum::<usize>(), 6);
+        assert!(result.support_histogram(0).is_empty());
+        assert!(FPResult::<&str>::from_patterns(vec![])
+            .support_histogram(3)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_tree_name() {
+        let mut tree = Tree::<&str>::new().with_name("conditional tree of a".to_string());
+        tree.add_transaction(vec!["b", "c"]);
+        assert_eq!(tree.name(), Some("conditional tree of a"));
+        assert_eq!(
+            tree.to_string(),
+            "Tree conditional tree of a:\n <(root)>\n  <Some(\"b\") 1 (node)>\n   <Some(\"c\") 1 (node)>\n"
+        );
+        assert_eq!(Tree::<&str>::new().name(), None);
+        assert_eq!(Tree::<&str>::new().to_string(), "Tree:\n <(root)>\n");
+    }
+
+    #[test]
+    fn test_find_frequent_patterns_lenient() {
+        let transactions = vec![
+            vec!["a", "b", "c"],
+            vec!["a", "bad", "c"],
+            vec!["a", "b"],
+            vec!["bad"],
+            vec!["b", "c"],
+        ];
+        let fp_growth = FPGrowth::<&str>::new(transactions, 2).with_item_normalizer(|item| {
+            assert_ne!(item, "bad", "malformed item");
+            item
+        });
+        let (result, skipped_transactions) = fp_growth.find_frequent_patterns_lenient();
+        assert_eq!(skipped_transactions, vec![1, 3]);
+        assert_eq!(result.transactions_num(), 3);
+        let mut patterns = result.frequent_patterns();
+        patterns.sort();
+        let mut expected =
+            FPGrowth::<&str>::new(vec![vec!["a", "b", "c"], vec!["a", "b"], vec!["b", "c"]], 2)
+                .find_frequent_patterns()
+                .frequent_patterns();
+        expected.sort();
+        assert_eq!(patterns, expected);
+
+        // The lenient mining could be repeated, and it's the same as the usual one if every
+        // transaction is valid.
+        let (again_result, again_skipped) = fp_growth.find_frequent_patterns_lenient();
+        assert_eq!(again_skipped, skipped_transactions);
+        assert_eq!(again_result.frequent_patterns_num(), patterns.len());
+        let fp_growth =
+            FPGrowth::<&str>::new(vec![vec!["a"], vec!["a"]], 2).with_item_normalizer(|item| item);
+        let (result, skipped_transactions) = fp_growth.find_frequent_patterns_lenient();
+        assert!(skipped_transactions.is_empty());
+        assert_eq!(result.frequent_patterns(), vec![(vec!["a"], 2)]);
+    }
+
+    #[test]
+    fn test_terminal_count() {
+        let mut tree = Tree::<&str>::new();
+        tree.add_transaction(vec!["a", "b", "c"]);
+        tree.add_transaction(vec!["a", "b"]);
+        tree.add_transaction(vec!["a", "b"]);
+        tree.add_transaction(vec!["a"]);
+        tree.add_transaction(vec!["a", "d"]);
+        let terminal_counts: Vec<(&str, usize)> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|&item| (item, tree.get_all_nodes(item)[0].terminal_count()))
+            .collect();
+        assert_eq!(
+            terminal_counts,
+            vec![("a", 1), ("b", 2), ("c", 1), ("d", 1)]
+        );
+        assert_eq!(tree.root().terminal_count(), 0);
+
+        let node = Node::new_rc(Some("a"), 1);
+        node.add_child(Node::new_rc(Some("b"), 3));
+        assert_eq!(node.terminal_count(), 0);
+    }
+
+    #[test]
+    fn test_generate_rules() {
+        let transactions = small_fixture_transactions();
+        let result = FPGrowth::<&str>::new(transactions, 2).find_frequent_patterns();
+        let rules = result.generate_rules(0.7);
+        // a => b: support(a, b) = 3 and support(a) = 4.
+        let rule = rules
+            .iter()
+            .find(|rule| rule.antecedent() == ["a"] && rule.consequent() == ["b"])
+            .unwrap();
+        assert_eq!(rule.support(), 3);
+        assert_eq!(rule.confidence(), 0.75);
+        assert!(rules.iter().all(|rule| rule.confidence() >= 0.7));
+        assert_eq!(rules, result.clone().into_rules(0.7));
+        assert_eq!(result.generate_rules(0.0).len(), 12);
+        assert!(result.generate_rules(1.1).is_empty());
+    }
+
+    #[test]
+    fn test_find_closed_patterns() {
+        let transactions = vec![
+            vec!["e", "c", "a", "b", "f", "h"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["e", "c", "a", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e", "e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+            vec!["g", "c", "e", "a"],
+            vec!["a", "c", "e", "g"],
+            vec!["i"],
+        ];
+        for minimum_support in 1..=9 {
+            let fp_growth = FPGrowth::<&str>::new(transactions.clone(), minimum_support);
+            assert_eq!(
+                fp_growth.find_closed_patterns(),
+                fp_growth.find_frequent_patterns().closed_patterns()
+            );
+        }
+        let random_transactions = random_transactions(300, 12, 6, 7);
+        let fp_growth = FPGrowth::new(random_transactions, 20);
+        assert_eq!(
+            fp_growth.find_closed_patterns(),
+            fp_growth.find_frequent_patterns().closed_patterns()
+        );
+    }
+
+    #[test]
+    fn test_find_maximal_patterns() {
+        // A pattern is maximal if none of its one-item extensions is frequent.
+        fn maximal_patterns_of<T: ItemType>(result: &FPResult<T>) -> Vec<(Vec<T>, usize)> {
+            let patterns = result.frequent_patterns();
+            let items: Vec<T> = patterns
+                .iter()
+                .filter(|(pattern, _)| pattern.len() == 1)
+                .map(|(pattern, _)| pattern[0])
+                .collect();
+            let mut maximal_patterns: Vec<(Vec<T>, usize)> = patterns
+                .into_iter()
+                .filter(|(pattern, _)| {
+                    items.iter().all(|item| {
+                        let mut extended_pattern = pattern.clone();
+                        extended_pattern.push(*item);
+                        pattern.contains(item) || result.support_of(&extended_pattern).is_none()
+                    })
+                })
+                .collect();
+            maximal_patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            maximal_patterns
+        }
+
+        let transactions = vec![
+            vec!["e", "c", "a", "b", "f", "h"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["e", "c", "a", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e", "e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+            vec!["g", "c", "e", "a"],
+            vec!["a", "c", "e", "g"],
+            vec!["i"],
+        ];
+        for minimum_support in 1..=9 {
+            let fp_growth = FPGrowth::<&str>::new(transactions.clone(), minimum_support);
+            let maximal_patterns = fp_growth.find_maximal_patterns();
+            assert_eq!(
+                maximal_patterns,
+                maximal_patterns_of(&fp_growth.find_frequent_patterns())
+            );
+            assert_eq!(maximal_patterns.len(), fp_growth.maximal_pattern_count());
+        }
+        for seed in 0..3 {
+            let fp_growth = FPGrowth::new(random_transactions(200, 12, 6, seed), 15);
+            assert_eq!(
+                fp_growth.find_maximal_patterns(),
+                maximal_patterns_of(&fp_growth.find_frequent_patterns())
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_top_k_patterns() {
+        fn top_k_of<T: ItemType>(result: &FPResult<T>, k: usize) -> Vec<(Vec<T>, usize)> {
+            let mut patterns = result.frequent_patterns();
+            patterns.iter_mut().for_each(|(pattern, _)| pattern.sort());
+            patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            patterns.truncate(k);
+            patterns
+        }
+
+        let transactions = vec![
+            vec!["e", "c", "a", "b", "f", "h"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["e", "c", "a", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+            vec!["g", "c", "e", "a"],
+            vec!["a", "c", "e", "g"],
+            vec!["i"],
+        ];
+        let fp_growth = FPGrowth::new(transactions.clone(), 1);
+        let result = fp_growth.find_frequent_patterns();
+        for k in 0..=result.frequent_patterns_num() + 1 {
+            assert_eq!(fp_growth.find_top_k_patterns(k), top_k_of(&result, k));
+        }
+        assert_eq!(
+            fp_growth.find_top_k_patterns(3),
+            vec![(vec!["a"], 8), (vec!["a", "c"], 8), (vec!["c"], 8)]
+        );
+
+        // The minimum support still holds.
+        let fp_growth = FPGrowth::new(transactions, 7);
+        assert_eq!(
+            fp_growth.find_top_k_patterns(100),
+            top_k_of(&fp_growth.find_frequent_patterns(), 100)
+        );
+        assert_eq!(fp_growth.find_top_k_patterns(100).len(), 4);
+
+        for seed in 0..3 {
+            let fp_growth = FPGrowth::new(random_transactions(200, 12, 6, seed), 2)
+                .include_singletons(seed != 0);
+            let result = fp_growth.find_frequent_patterns();
+            for k in [1, 10, 50, 200] {
+                assert_eq!(fp_growth.find_top_k_patterns(k), top_k_of(&result, k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_relative_minimum_support() {
+        let transactions = vec![
+            vec!["e", "c", "a", "b", "f", "h"],
+            vec!["a", "c", "g"],
+            vec!["e"],
+            vec!["e", "c", "a", "g", "d"],
+            vec!["a", "c", "e", "g"],
+            vec!["e"],
+            vec!["a", "c", "e", "b", "f"],
+            vec!["a", "c", "d"],
+            vec!["g", "c", "e", "a"],
+            vec!["a", "c", "e", "g"],
+            vec!["i"],
+        ];
+        assert_eq!(Support::from(3), Support::Absolute(3));
+        assert_eq!(Support::from(0.5), Support::Relative(0.5));
+        assert_eq!(Support::Relative(0.25).absolute(11), 3);
+        assert_eq!(Support::Relative(0.005).absolute(1000), 5);
+        assert_eq!(Support::Relative(-1.0).absolute(11), 0);
+        assert_eq!(Support::Absolute(4).absolute(11), 4);
+
+        // 0.25 of the 11 transactions is rounded up to 3.
+        let expected =
+            sorted_patterns(&FPGrowth::new(transactions.clone(), 3).find_frequent_patterns());
+        let fp_growth = FPGrowth::new(transactions.clone(), 0.25);
+        assert_eq!(
+            sorted_patterns(&fp_growth.find_frequent_patterns()),
+            expected
+        );
+        let fp_growth = FPGrowth::new(transactions.clone(), Support::Absolute(11))
+            .with_minimum_support(Support::Relative(0.25));
+        assert_eq!(
+            sorted_patterns(&fp_growth.find_frequent_patterns()),
+            expected
+        );
+        let aggregated = transactions.iter().map(|t| (t.clone(), 2)).collect();
+        let fp_growth = FPGrowth::from_aggregated(aggregated, 0.25);
+        assert_eq!(
+            fp_growth.find_frequent_patterns().frequent_patterns().len(),
+            expected.len()
+        );
+
+        assert_eq!(
+            FPGrowth::try_new(transactions, 1.5).err(),
+            Some(Error::SupportExceedsTransactions {
+                minimum_support: 17,
+                transactions_num: 11,
+            })
+        );
+    }
+
+    #[test]
+    fn test_pattern_length() {
+        let transactions = random_transactions(200, 12, 6, 3);
+        let all_patterns =
+            sorted_patterns(&FPGrowth::new(transactions.clone(), 5).find_frequent_patterns());
+        for (min_length, max_length) in [(1, 1), (2, 3), (3, 3), (2, 12)] {
+            let expected: Vec<(Vec<u32>, usize)> = all_patterns
+                .iter()
+                .filter(|(pattern, _)| (min_length..=max_length).contains(&pattern.len()))
+                .cloned()
+                .collect();
+            let fp_growth =
+                FPGrowth::new(transactions.clone(), 5).with_pattern_length(min_length, max_length);
+            assert_eq!(
+                sorted_patterns(&fp_growth.find_frequent_patterns()),
+                expected
+            );
+            assert_eq!(
+                sorted_patterns(&fp_growth.find_pairs()),
+                expected
+                    .iter()
+                    .filter(|(pattern, _)| pattern.len() <= 2)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            );
+            assert!(fp_growth
+                .find_top_k_patterns(20)
+                .iter()
+                .all(|(pattern, _)| (min_length..=max_length).contains(&pattern.len())));
+        }
+    }
+
+    #[test]
+    fn test_pattern_length_bounds() {
+        let transactions = random_transactions(200, 12, 6, 3);
+        let all_patterns =
+            sorted_patterns(&FPGrowth::new(transactions.clone(), 5).find_frequent_patterns());
+        // The max length and the max depth overwrite each other, the last one set wins.
+        let fp_growth = FPGrowth::new(transactions.clone(), 5)
+            .with_max_depth(1)
+            .with_pattern_length(2, 3);
+        assert!(sorted_patterns(&fp_growth.find_frequent_patterns())
+            .iter()
+            .any(|(pattern, _)| pattern.len() == 3));
+        let fp_growth = FPGrowth::new(transactions.clone(), 5)
+            .with_pattern_length(1, 3)
+            .with_max_depth(2);
+        assert_eq!(
+            sorted_patterns(&fp_growth.find_frequent_patterns()),
+            all_patterns
+                .iter()
+                .filter(|(pattern, _)| pattern.len() <= 2)
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+
+        let result = std::panic::catch_unwind(|| {
+            FPGrowth::new(transactions, 5).with_pattern_length(4, 2);
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_anchor_items() {
+        let transactions = random_transactions(200, 12, 6, 5);
+        let all_patterns =
+            sorted_patterns(&FPGrowth::new(transactions.clone(), 4).find_frequent_patterns());
+        for anchor_items in [vec![], vec![3], vec![0, 7], vec![1, 2, 11]] {
+            let expected: Vec<(Vec<u32>, usize)> = all_patterns
+                .iter()
+                .filter(|(pattern, _)| anchor_items.iter().all(|item| pattern.contains(item)))
+                .cloned()
+                .collect();
+            let fp_growth = FPGrowth::new(transactions.clone(), 4)
+                .with_anchor_items(anchor_items.iter().copied().collect());
+            assert_eq!(
+                sorted_patterns(&fp_growth.find_frequent_patterns()),
+                expected
+            );
+            assert_eq!(
+                sorted_patterns(&fp_growth.find_pairs()),
+                expected
+                    .iter()
+                    .filter(|(pattern, _)| pattern.len() <= 2)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            );
+            let mut top_k_patterns = expected.clone();
+            top_k_patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            top_k_patterns.truncate(10);
+            assert_eq!(fp_growth.find_top_k_patterns(10), top_k_patterns);
+            assert!(fp_growth
+                .find_maximal_patterns()
+                .iter()
+                .all(|(pattern, _)| anchor_items.iter().all(|item| pattern.contains(item))));
+
+            let mut window_miner = fp_growth.window_miner(transactions.clone(), 200, 200);
+            assert_eq!(sorted_patterns(&window_miner.next().unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn test_excluded_items() {
+        let transactions = random_transactions(200, 12, 6, 9);
+        let excluded_items: HashSet<u32> = [2, 5, 8].iter().copied().collect();
+        let cleaned_transactions: Vec<Vec<u32>> = transactions
+            .iter()
+            .map(|transaction| {
+                transaction
+                    .iter()
+                    .copied()
+                    .filter(|item| !excluded_items.contains(item))
+                    .collect()
+            })
+            .collect();
+        let expected = FPGrowth::new(cleaned_transactions, 4).find_frequent_patterns();
+
+        let fp_growth =
+            FPGrowth::new(transactions.clone(), 4).with_excluded_items(excluded_items.clone());
+        let result = fp_growth.find_frequent_patterns();
+        assert_eq!(sorted_patterns(&result), sorted_patterns(&expected));
+        assert_eq!(
+            result.elimination_sets_sorted(),
+            expected.elimination_sets_sorted()
+        );
+        assert!(result
+            .frequent_patterns()
+            .iter()
+            .all(|(pattern, _)| pattern.iter().all(|item| !excluded_items.contains(item))));
+        assert_eq!(fp_growth.transactions_containing(&[2]), 0);
+
+        let fp_growth =
+            FPGrowth::new(transactions, 4).with_item_exclusion(|&item| item % 3 == 2 && item < 10);
+        assert_eq!(
+            sorted_patterns(&fp_growth.find_frequent_patterns()),
+            sorted_patterns(&expected)
+        );
+
+        // An excluded ancestor is never augmented, and an excluded item adds no support to it.
+        let transactions = vec![vec!["skim", "bread"], vec!["whole", "bread"], vec!["skim"]];
+        let parents: HashMap<&str, &str> = [("skim", "milk"), ("whole", "milk")]
+            .iter()
+            .copied()
+            .collect();
+        let fp_growth = FPGrowth::new(transactions.clone(), 1)
+            .with_item_hierarchy(parents.clone())
+            .unwrap()
+            .with_excluded_items(["milk"].iter().copied().collect());
+        assert_eq!(
+            fp_growth.find_frequent_patterns().support_of(&["milk"]),
+            None
+        );
+        let fp_growth = FPGrowth::new(transactions, 1)
+            .with_item_hierarchy(parents)
+            .unwrap()
+            .with_excluded_items(["skim"].iter().copied().collect());
+        let result = fp_growth.find_frequent_patterns();
+        assert_eq!(result.support_of(&["milk"]), Some(1));
+        assert_eq!(result.support_of(&["skim"]), None);
+    }
 }
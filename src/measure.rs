@@ -0,0 +1,160 @@
+//! `measure` implements the interestingness measures to rank the frequent patterns.
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::algorithm::FPResult;
+use crate::ItemType;
+
+/// `InterestMeasure` represents the common measures of how interesting a frequent pattern is,
+/// all of them are computed from the supports the result already knows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterestMeasure {
+    /// The absolute support of the pattern.
+    Support,
+    /// The support of the pattern divided by the number of transactions.
+    RelativeSupport,
+    /// The support of the pattern divided by the maximum support of its items.
+    AllConfidence,
+    /// The relative support of the pattern divided by the product of the relative supports of
+    /// its items, a value larger than 1.0 means the items are positively correlated.
+    Lift,
+    /// The mean of the support of the pattern divided by the support of every item, i.e. the
+    /// mean confidence of the rules from an item to the others. It is null-invariant.
+    Kulczynski,
+    /// The support of the pattern divided by the geometric mean of the supports of its items,
+    /// i.e. `support / sqrt(sup_a * sup_b)` for a pair. It is null-invariant.
+    Cosine,
+}
+
+/// `Combine` represents how the weights of the items in a pattern are combined into one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Combine {
+    /// The sum of the item weights.
+    Sum,
+    /// The product of the item weights.
+    Product,
+    /// The arithmetic mean of the item weights.
+    Mean,
+}
+
+impl Combine {
+    /// Combine the given item `weights` of a non-empty pattern.
+    fn apply(self, weights: &[f64]) -> f64 {
+        match self {
+            Combine::Sum => weights.iter().sum(),
+            Combine::Product => weights.iter().product(),
+            Combine::Mean => weights.iter().sum::<f64>() / weights.len() as f64,
+        }
+    }
+}
+
+impl<T: ItemType> FPResult<T> {
+    /// Compute the given interestingness `measure` of a frequent pattern. `None` is returned if
+    /// it is not a frequent pattern, or the measure needs the number of transactions while
+    /// this result isn't mined from transactions.
+    pub fn interest_of(&self, pattern: &[T], measure: InterestMeasure) -> Option<f64> {
+        let support = self.support_of(pattern)? as f64;
+        let transactions_num = self.transactions_num as f64;
+        let item_supports = pattern
+            .iter()
+            .map(|&item| self.item_support(item).map(|support| support as f64))
+            .collect::<Option<Vec<f64>>>()?;
+        match measure {
+            InterestMeasure::Support => Some(support),
+            InterestMeasure::RelativeSupport if transactions_num > 0.0 => {
+                Some(support / transactions_num)
+            }
+            InterestMeasure::AllConfidence => {
+                let max_item_support = item_supports.iter().cloned().fold(0.0, f64::max);
+                Some(support / max_item_support)
+            }
+            InterestMeasure::Lift if transactions_num > 0.0 => {
+                let expected_support = item_supports
+                    .iter()
+                    .fold(transactions_num, |expected, item_support| {
+                        expected * item_support / transactions_num
+                    });
+                Some(support / expected_support)
+            }
+            InterestMeasure::Kulczynski => {
+                let confidence_sum: f64 = item_supports
+                    .iter()
+                    .map(|item_support| support / item_support)
+                    .sum();
+                Some(confidence_sum / item_supports.len() as f64)
+            }
+            InterestMeasure::Cosine => {
+                // Take the geometric mean in logarithms to keep the product from overflowing.
+                let log_mean = item_supports
+                    .iter()
+                    .map(|item_support| item_support.ln())
+                    .sum::<f64>()
+                    / item_supports.len() as f64;
+                Some(support / log_mean.exp())
+            }
+            _ => None,
+        }
+    }
+
+    /// Rank the frequent patterns by the given interestingness `measure` in descending order,
+    /// the patterns whose measure could not be computed are left out.
+    pub fn rank_by(&self, measure: InterestMeasure) -> Vec<(Vec<T>, f64)> {
+        let mut ranked_patterns: Vec<(Vec<T>, f64)> = self
+            .frequent_patterns
+            .iter()
+            .filter_map(|(pattern, _)| {
+                self.interest_of(pattern, measure)
+                    .map(|value| (pattern.to_vec(), value))
+            })
+            .collect();
+        ranked_patterns.sort_by(|a, b| descending(a.1, b.1));
+        ranked_patterns
+    }
+
+    /// Return the `n` frequent pairs of items ranked highest by the given `measure`, the items
+    /// of every pair are sorted. The pairs whose measure could not be computed are left out.
+    pub fn top_correlated_pairs(&self, n: usize, measure: InterestMeasure) -> Vec<((T, T), f64)> {
+        let mut ranked_pairs: Vec<((T, T), f64)> = self
+            .frequent_patterns
+            .iter()
+            .filter(|(pattern, _)| pattern.len() == 2)
+            .filter_map(|(pattern, _)| {
+                self.interest_of(pattern, measure)
+                    .map(|value| ((pattern[0], pattern[1]), value))
+            })
+            .collect();
+        ranked_pairs.sort_by(|a, b| descending(a.1, b.1));
+        ranked_pairs.truncate(n);
+        ranked_pairs
+    }
+
+    /// Rank the frequent patterns in descending order by their support times the item `weights`
+    /// combined with `combine`, so the patterns of the high-value items could come first.
+    /// The items without a weight are weighted as 0.0.
+    pub fn rank_by_item_weights(
+        &self,
+        weights: &HashMap<T, f64>,
+        combine: Combine,
+    ) -> Vec<(Vec<T>, f64)> {
+        let mut ranked_patterns: Vec<(Vec<T>, f64)> = self
+            .frequent_patterns
+            .iter()
+            .filter(|(pattern, _)| !pattern.is_empty())
+            .map(|(pattern, support)| {
+                let item_weights: Vec<f64> = pattern
+                    .iter()
+                    .map(|item| weights.get(item).copied().unwrap_or(0.0))
+                    .collect();
+                let value = combine.apply(&item_weights) * *support as f64;
+                (pattern.to_vec(), value)
+            })
+            .collect();
+        ranked_patterns.sort_by(|a, b| descending(a.1, b.1));
+        ranked_patterns
+    }
+}
+
+/// Compare two measure values to sort them in descending order.
+pub(crate) fn descending(a: f64, b: f64) -> Ordering {
+    b.total_cmp(&a)
+}
@@ -0,0 +1,19 @@
+//! `prelude` re-exports the commonly used types of this crate, so that they could be
+//! imported with a single `use fp_growth::prelude::*`.
+
+pub use crate::algorithm::{
+    FPGrowth, FPResult, InputReport, PatternExplanation, PatternStats, SortKey, Support,
+    SupportPredicate, WindowMiner,
+};
+pub use crate::closure::ClosureFamily;
+pub use crate::compact::CompactTree;
+pub use crate::error::Error;
+pub use crate::itemset::Itemset;
+pub use crate::measure::{Combine, InterestMeasure};
+pub use crate::rule::AssociationRule;
+pub use crate::sequence::PrefixSpan;
+pub use crate::source::{
+    AggregatedTransactions, BitsetTransactions, DiskTransactions, Transaction, TransactionSource,
+};
+pub use crate::tree::{FrozenTree, HeaderRow, Node, NodePool, Tree};
+pub use crate::ItemType;
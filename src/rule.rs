@@ -0,0 +1,273 @@
+//! `rule` implements the association rules generated from the frequent patterns.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    ops::Deref,
+};
+
+use crate::algorithm::FPResult;
+use crate::closure::is_subset;
+use crate::ItemType;
+
+/// `AssociationRule<T>` represents a rule `antecedent => consequent`, which means the transactions
+/// containing the `antecedent` tend to contain the `consequent` as well.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssociationRule<T> {
+    antecedent: Vec<T>,
+    consequent: Vec<T>,
+    support: usize,
+    confidence: f64,
+}
+
+impl<T: ItemType> AssociationRule<T> {
+    /// Create an association rule with the given `antecedent`, `consequent`, the `support` of
+    /// both of them and its `confidence`.
+    pub fn new(
+        antecedent: Vec<T>,
+        consequent: Vec<T>,
+        support: usize,
+        confidence: f64,
+    ) -> AssociationRule<T> {
+        AssociationRule {
+            antecedent,
+            consequent,
+            support,
+            confidence,
+        }
+    }
+
+    pub fn antecedent(&self) -> &[T] {
+        &self.antecedent
+    }
+
+    pub fn consequent(&self) -> &[T] {
+        &self.consequent
+    }
+
+    /// Return the support of the union of the antecedent and the consequent.
+    pub fn support(&self) -> usize {
+        self.support
+    }
+
+    /// Return the confidence of this rule, i.e. `support(antecedent ∪ consequent) / support(antecedent)`.
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+}
+
+impl<T: ItemType> FPResult<T> {
+    /// Generate all association rules `antecedent => consequent` whose confidence is not lower
+    /// than `min_confidence`, every rule comes from a frequent pattern split into two non-empty
+    /// parts. The confidence is computed from the supports of the pattern and the antecedent.
+    pub fn generate_rules(&self, min_confidence: f64) -> Vec<AssociationRule<T>> {
+        generate_rules(&self.frequent_patterns, min_confidence)
+    }
+
+    /// Consume the result and generate all association rules whose confidence is not lower
+    /// than `min_confidence`, see [`FPResult::generate_rules()`]. The items are still copied
+    /// into the rules, the result is only dropped afterwards.
+    pub fn into_rules(self, min_confidence: f64) -> Vec<AssociationRule<T>> {
+        self.generate_rules(min_confidence)
+    }
+
+    /// Return a lazy iterator over the association rules whose lift is not lower than `min_lift`,
+    /// the lift of a rule is its confidence divided by the relative support of the consequent,
+    /// a value larger than 1.0 means the antecedent and the consequent are positively correlated.
+    ///
+    /// The rules are generated one by one as the iterator is consumed. No rule is generated if
+    /// the number of transactions is unknown, i.e. this result isn't mined from transactions.
+    pub fn rules_with_min_lift(
+        &self,
+        min_lift: f64,
+    ) -> impl Iterator<Item = AssociationRule<T>> + '_ {
+        self.rules_with_lift()
+            .filter(move |(_, lift)| *lift >= min_lift)
+            .map(|(rule, _)| rule)
+    }
+
+    /// Return the `k` association rules with the highest lift whose confidence is not lower
+    /// than `min_confidence`, sorted by lift in descending order. The ties are broken by the
+    /// antecedent and then the consequent, so the result is deterministic.
+    ///
+    /// Only `k` rules are kept in a bounded heap while the rules are generated, so the memory
+    /// does not grow with the number of rules.
+    pub fn top_k_rules_by_lift(&self, k: usize, min_confidence: f64) -> Vec<AssociationRule<T>> {
+        if k == 0 {
+            return vec![];
+        }
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for (rule, lift) in self.rules_with_lift() {
+            if rule.confidence < min_confidence {
+                continue;
+            }
+            heap.push(RankedRule { rule, lift });
+            if heap.len() > k {
+                // The top of the heap is the lowest ranked rule.
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|ranked_rule| ranked_rule.rule)
+            .collect()
+    }
+
+    /// Return the minimal non-redundant rule base whose confidence is not lower than
+    /// `min_confidence`, sorted by the antecedent and then the consequent. Every rule goes
+    /// from a generator to a closed pattern which is a proper superset of it, i.e. it has the
+    /// smallest antecedent and the largest consequent among the rules of the same support and
+    /// confidence, from which all the other rules could be derived.
+    ///
+    /// The rules from the generators to their own closures are the exact rules with the
+    /// confidence of 1.0, the others are the approximate ones.
+    pub fn minimal_rule_base(&self, min_confidence: f64) -> Vec<AssociationRule<T>> {
+        let closed_patterns = self.closed_patterns();
+        let mut rules = vec![];
+        for (generator, generator_support) in self.generators().into_iter() {
+            for (closed_pattern, support) in closed_patterns.iter() {
+                if closed_pattern.len() <= generator.len() || !is_subset(&generator, closed_pattern)
+                {
+                    continue;
+                }
+                let confidence = *support as f64 / generator_support as f64;
+                if confidence >= min_confidence {
+                    let consequent = closed_pattern
+                        .iter()
+                        .filter(|item| !generator.contains(item))
+                        .copied()
+                        .collect();
+                    rules.push(AssociationRule::new(
+                        generator.clone(),
+                        consequent,
+                        *support,
+                        confidence,
+                    ));
+                }
+            }
+        }
+        rules.sort_by(|a, b| {
+            a.antecedent
+                .cmp(&b.antecedent)
+                .then_with(|| a.consequent.cmp(&b.consequent))
+        });
+        rules
+    }
+
+    /// Return a lazy iterator over all association rules with their lift.
+    fn rules_with_lift(&self) -> impl Iterator<Item = (AssociationRule<T>, f64)> + '_ {
+        let transactions_num = self.transactions_num as f64;
+        self.frequent_patterns
+            .iter()
+            .filter(move |_| transactions_num > 0.0)
+            .flat_map(move |(pattern, support)| {
+                // Every non-empty proper subset of the pattern could be an antecedent.
+                Splits::new(pattern).filter_map(move |(antecedent, consequent)| {
+                    let antecedent_support = self.support_of(&antecedent)?;
+                    let consequent_support = self.support_of(&consequent)?;
+                    let confidence = *support as f64 / antecedent_support as f64;
+                    let lift = confidence * transactions_num / consequent_support as f64;
+                    let rule = AssociationRule::new(antecedent, consequent, *support, confidence);
+                    Some((rule, lift))
+                })
+            })
+    }
+}
+
+/// `RankedRule<T>` orders the rules by their lift in descending order, then by the antecedent
+/// and the consequent, i.e. the greater one is ranked lower.
+struct RankedRule<T> {
+    rule: AssociationRule<T>,
+    lift: f64,
+}
+
+impl<T: ItemType> Ord for RankedRule<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .lift
+            .total_cmp(&self.lift)
+            .then_with(|| self.rule.antecedent.cmp(&other.rule.antecedent))
+            .then_with(|| self.rule.consequent.cmp(&other.rule.consequent))
+    }
+}
+
+impl<T: ItemType> PartialOrd for RankedRule<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ItemType> PartialEq for RankedRule<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: ItemType> Eq for RankedRule<T> {}
+
+/// Generate the association rules from the frequent patterns whose items are sorted.
+pub(crate) fn generate_rules<T: ItemType, P: Deref<Target = [T]>>(
+    sorted_patterns: &[(P, usize)],
+    min_confidence: f64,
+) -> Vec<AssociationRule<T>> {
+    let supports: HashMap<&[T], usize> = sorted_patterns
+        .iter()
+        .map(|(pattern, support)| (&**pattern, *support))
+        .collect();
+    let mut rules = vec![];
+    for (pattern, support) in sorted_patterns.iter() {
+        // Every non-empty proper subset of the pattern could be an antecedent.
+        for (antecedent, consequent) in Splits::new(pattern) {
+            if let Some(&antecedent_support) = supports.get(antecedent.as_slice()) {
+                let confidence = *support as f64 / antecedent_support as f64;
+                if confidence >= min_confidence {
+                    rules.push(AssociationRule::new(
+                        antecedent, consequent, *support, confidence,
+                    ));
+                }
+            }
+        }
+    }
+    rules
+}
+
+/// `Splits<'a, T>` iterates over every split of a pattern into two non-empty parts. The items
+/// in the first part are marked by a binary counter as wide as the pattern, which counts up
+/// from 1 until all items are marked, so a pattern of any length is split, though the number
+/// of splits doubles with every item.
+struct Splits<'a, T> {
+    pattern: &'a [T],
+    selected: Vec<bool>,
+}
+
+impl<'a, T: ItemType> Splits<'a, T> {
+    fn new(pattern: &'a [T]) -> Splits<'a, T> {
+        Splits {
+            pattern,
+            selected: vec![false; pattern.len()],
+        }
+    }
+}
+
+impl<T: ItemType> Iterator for Splits<'_, T> {
+    type Item = (Vec<T>, Vec<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Add 1 to the counter, the lowest unmarked item is marked and the ones below cleared.
+        let index = self.selected.iter().position(|&selected| !selected)?;
+        self.selected[..index].fill(false);
+        self.selected[index] = true;
+        if self.selected.iter().all(|&selected| selected) {
+            return None;
+        }
+        let mut selected = vec![];
+        let mut rest = vec![];
+        for (&item, &is_selected) in self.pattern.iter().zip(self.selected.iter()) {
+            match is_selected {
+                true => selected.push(item),
+                false => rest.push(item),
+            }
+        }
+        Some((selected, rest))
+    }
+}
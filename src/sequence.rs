@@ -0,0 +1,85 @@
+//! `sequence` implements the PrefixSpan algorithm to mine the frequent sequences, where the
+//! order of the items matters, e.g. the clickstreams or any other event sequences.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::ItemType;
+
+/// `PrefixSpan<T>` represents a PrefixSpan algorithm instance over the given sequences.
+/// A sequence supports a pattern if the items of the pattern appear in the sequence in the
+/// same order, not necessarily next to each other. Every sequence counts at most once for a
+/// pattern, just like a transaction does for a frequent pattern.
+#[derive(Clone, Debug)]
+pub struct PrefixSpan<T> {
+    sequences: Vec<Vec<T>>,
+}
+
+impl<T: ItemType> PrefixSpan<T> {
+    /// Create a PrefixSpan algorithm instance with the given sequences.
+    pub fn new(sequences: Vec<Vec<T>>) -> PrefixSpan<T> {
+        PrefixSpan { sequences }
+    }
+
+    /// Find the frequent sequences whose support is not lower than `minimum_support`, with
+    /// their supports. They are returned in the depth-first order of the pattern growth with
+    /// the items in ascending order, i.e. every sequence comes right before its extensions.
+    pub fn find_frequent_sequences(&self, minimum_support: usize) -> Vec<(Vec<T>, usize)> {
+        let mut frequent_sequences = vec![];
+        // The initial projected database holds every sequence from its beginning.
+        let projection: Vec<(usize, usize)> = (0..self.sequences.len()).map(|i| (i, 0)).collect();
+        self.find_with_prefix(
+            &mut vec![],
+            &projection,
+            minimum_support,
+            &mut frequent_sequences,
+        );
+        frequent_sequences
+    }
+
+    /// Grow the `prefix` by every frequent item in its `projection`, which holds the sequence
+    /// index and the position right after the prefix in every sequence supporting it.
+    fn find_with_prefix(
+        &self,
+        prefix: &mut Vec<T>,
+        projection: &[(usize, usize)],
+        minimum_support: usize,
+        frequent_sequences: &mut Vec<(Vec<T>, usize)>,
+    ) {
+        let mut item_supports: HashMap<T, usize> = HashMap::new();
+        let mut suffix_items: HashSet<T> = HashSet::new();
+        for &(index, position) in projection.iter() {
+            // Count an item at most once in a suffix.
+            suffix_items.clear();
+            suffix_items.extend(self.sequences[index][position..].iter().copied());
+            for &item in suffix_items.iter() {
+                *item_supports.entry(item).or_default() += 1;
+            }
+        }
+        let mut frequent_items: Vec<(T, usize)> = item_supports
+            .into_iter()
+            .filter(|&(_, support)| support >= minimum_support)
+            .collect();
+        frequent_items.sort_unstable();
+
+        for (item, support) in frequent_items.into_iter() {
+            prefix.push(item);
+            frequent_sequences.push((prefix.clone(), support));
+            let item_projection: Vec<(usize, usize)> = projection
+                .iter()
+                .filter_map(|&(index, position)| {
+                    self.sequences[index][position..]
+                        .iter()
+                        .position(|&suffix_item| suffix_item == item)
+                        .map(|offset| (index, position + offset + 1))
+                })
+                .collect();
+            self.find_with_prefix(
+                prefix,
+                &item_projection,
+                minimum_support,
+                frequent_sequences,
+            );
+            prefix.pop();
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! `source` abstracts where the transactions come from, so that the algorithm could scan them
+//! in several passes without holding them all in memory.
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap, HashSet},
+    fs::File,
+    io::{self, BufRead, BufReader},
+    iter,
+    path::{Path, PathBuf},
+};
+
+use crate::error::Error;
+use crate::ItemType;
+
+/// `TransactionSource<T>` provides the transactions to mine, it should yield the same
+/// transactions in the same order every time they are iterated.
+pub trait TransactionSource<T: ItemType> {
+    /// Return an iterator over all transactions.
+    fn transactions(&self) -> Box<dyn Iterator<Item = Cow<'_, [T]>> + '_>;
+
+    /// Return the number of transactions.
+    fn transactions_num(&self) -> usize {
+        self.transactions().count()
+    }
+
+    /// Count the support of every item, an item is counted at most once in a transaction.
+    fn item_supports(&self) -> HashMap<T, usize> {
+        count_item_supports(self.transactions())
+    }
+
+    /// Return an iterator over the transactions with the numbers of their occurrences, the
+    /// same transactions could be yielded once with their total number rather than repeatedly,
+    /// so they are added to the tree at once. Every transaction occurs once by default.
+    fn weighted_transactions(&self) -> Box<dyn Iterator<Item = (Cow<'_, [T]>, usize)> + '_> {
+        Box::new(self.transactions().map(|transaction| (transaction, 1)))
+    }
+}
+
+/// `Transaction<T>` is a single transaction of the items `T`, a vector of any type implementing
+/// it is a [`TransactionSource`], so it could be mined with [`crate::algorithm::FPGrowth::from_source()`]
+/// directly, e.g. a `Vec<BTreeSet<T>>` or a vector of the user-defined transaction structs.
+pub trait Transaction<T: ItemType> {
+    /// Return an iterator over the items of this transaction.
+    fn items(&self) -> impl Iterator<Item = T>;
+
+    /// Return the items as a slice if they are stored contiguously, so they could be borrowed
+    /// rather than collected during every pass.
+    fn as_slice(&self) -> Option<&[T]> {
+        None
+    }
+}
+
+impl<T: ItemType> Transaction<T> for Vec<T> {
+    fn items(&self) -> impl Iterator<Item = T> {
+        self.iter().copied()
+    }
+
+    fn as_slice(&self) -> Option<&[T]> {
+        Some(self)
+    }
+}
+
+impl<T: ItemType> Transaction<T> for BTreeSet<T> {
+    fn items(&self) -> impl Iterator<Item = T> {
+        self.iter().copied()
+    }
+}
+
+impl<T: ItemType> Transaction<T> for HashSet<T> {
+    fn items(&self) -> impl Iterator<Item = T> {
+        self.iter().copied()
+    }
+}
+
+impl<T: ItemType, X: Transaction<T>> TransactionSource<T> for Vec<X> {
+    fn transactions(&self) -> Box<dyn Iterator<Item = Cow<'_, [T]>> + '_> {
+        Box::new(self.iter().map(|transaction| match transaction.as_slice() {
+            Some(items) => Cow::Borrowed(items),
+            None => Cow::Owned(transaction.items().collect()),
+        }))
+    }
+
+    fn transactions_num(&self) -> usize {
+        self.len()
+    }
+}
+
+/// `AggregatedTransactions<T>` holds the distinct transactions with the numbers of their
+/// occurrences, e.g. the baskets already aggregated upstream. Every transaction is added to the
+/// tree once with its number, and the result is the same as mining the expanded transactions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AggregatedTransactions<T> {
+    transactions: Vec<(Vec<T>, usize)>,
+}
+
+impl<T: ItemType> AggregatedTransactions<T> {
+    /// Create the aggregated transactions from the `(transaction, count)` pairs, the pairs
+    /// whose count is 0 are ignored like they don't occur at all.
+    pub fn new(transactions: Vec<(Vec<T>, usize)>) -> AggregatedTransactions<T> {
+        AggregatedTransactions { transactions }
+    }
+}
+
+impl<T: ItemType> TransactionSource<T> for AggregatedTransactions<T> {
+    /// Yield every transaction as many times as it occurs.
+    fn transactions(&self) -> Box<dyn Iterator<Item = Cow<'_, [T]>> + '_> {
+        Box::new(self.transactions.iter().flat_map(|(transaction, count)| {
+            iter::repeat(Cow::Borrowed(transaction.as_slice())).take(*count)
+        }))
+    }
+
+    fn transactions_num(&self) -> usize {
+        self.transactions.iter().map(|(_, count)| count).sum()
+    }
+
+    fn item_supports(&self) -> HashMap<T, usize> {
+        count_weighted_item_supports(self.weighted_transactions())
+    }
+
+    fn weighted_transactions(&self) -> Box<dyn Iterator<Item = (Cow<'_, [T]>, usize)> + '_> {
+        Box::new(
+            self.transactions
+                .iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(transaction, count)| (Cow::Borrowed(transaction.as_slice()), *count)),
+        )
+    }
+}
+
+/// `DiskTransactions` reads the transactions lazily from a line-oriented file, every line is
+/// a transaction holding the integer item IDs separated by whitespaces. Only one line is kept
+/// in memory at a time, so the file could be larger than the memory.
+///
+/// The file is read again in every pass of the mining, so it must not change between them.
+/// Only as many lines as counted when it's opened are read, and a pass panics if the file
+/// fails to be opened, read or parsed again, rather than leaving the results silently wrong.
+#[derive(Clone, Debug)]
+pub struct DiskTransactions {
+    path: PathBuf,
+    transactions_num: usize,
+}
+
+impl DiskTransactions {
+    /// Open the transaction file at `path`, the whole file is scanned once to validate it.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<DiskTransactions> {
+        let path = path.as_ref().to_path_buf();
+        let mut transactions_num = 0;
+        for (index, line) in BufReader::new(File::open(&path)?).lines().enumerate() {
+            parse_transaction(&line?).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {}", index + 1, err),
+                )
+            })?;
+            transactions_num += 1;
+        }
+        Ok(DiskTransactions {
+            path,
+            transactions_num,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl TransactionSource<u32> for DiskTransactions {
+    /// Read the file again from the beginning. Since the file has been validated when it's
+    /// opened, it panics if the file can't be read any more, e.g. it's removed.
+    fn transactions(&self) -> Box<dyn Iterator<Item = Cow<'_, [u32]>> + '_> {
+        let mut lines = match File::open(&self.path) {
+            Ok(file) => BufReader::new(file).lines(),
+            Err(err) => panic!(
+                "the transaction file {:?} can't be opened again: {}",
+                self.path, err
+            ),
+        };
+        let mut read_num = 0;
+        Box::new(
+            iter::from_fn(move || {
+                // The lines appended since the file is opened are ignored.
+                if read_num == self.transactions_num {
+                    return None;
+                }
+                let transaction = lines
+                    .next()
+                    .and_then(|line| parse_transaction(&line.ok()?).ok());
+                match transaction {
+                    Some(transaction) => {
+                        read_num += 1;
+                        Some(transaction)
+                    }
+                    None => panic!(
+                        "the transaction file {:?} fails to be read again at line {}",
+                        self.path,
+                        read_num + 1
+                    ),
+                }
+            })
+            .map(Cow::Owned),
+        )
+    }
+
+    fn transactions_num(&self) -> usize {
+        self.transactions_num
+    }
+}
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// `BitsetTransactions` holds the transactions of the dense integer items in
+/// `0..universe_size` as bitsets, every transaction takes the same number of 64-bit words.
+/// It's much more compact than `Vec<Vec<u32>>` when the transactions are long compared to
+/// the universe, and the item counting and containment checks work on whole words.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitsetTransactions {
+    universe_size: usize,
+    // words_per_transaction is the number of words a single transaction takes.
+    words_per_transaction: usize,
+    words: Vec<u64>,
+    transactions_num: usize,
+}
+
+impl BitsetTransactions {
+    /// Create an empty set of transactions whose items are all lower than `universe_size`.
+    pub fn new(universe_size: usize) -> BitsetTransactions {
+        BitsetTransactions {
+            universe_size,
+            words_per_transaction: universe_size.div_ceil(WORD_BITS),
+            words: vec![],
+            transactions_num: 0,
+        }
+    }
+
+    /// Create the bitset transactions from the given `transactions`,
+    /// [`Error::ItemOutOfUniverse`] is returned if any item is not lower than `universe_size`.
+    pub fn from_transactions(
+        transactions: &[Vec<u32>],
+        universe_size: usize,
+    ) -> Result<BitsetTransactions, Error> {
+        let mut bitset_transactions = BitsetTransactions::new(universe_size);
+        for transaction in transactions.iter() {
+            bitset_transactions.push(transaction)?;
+        }
+        Ok(bitset_transactions)
+    }
+
+    /// Append a transaction, the duplicated items are stored only once.
+    /// [`Error::ItemOutOfUniverse`] is returned if any item is not lower than the universe size,
+    /// in which case nothing is appended.
+    pub fn push(&mut self, transaction: &[u32]) -> Result<(), Error> {
+        let bitset = self.to_bitset(transaction)?;
+        self.words.extend(bitset);
+        self.transactions_num += 1;
+        Ok(())
+    }
+
+    pub fn universe_size(&self) -> usize {
+        self.universe_size
+    }
+
+    /// Return the number of transactions which contain all items of the given `itemset`,
+    /// no transaction contains an item out of the universe.
+    pub fn transactions_containing(&self, itemset: &[u32]) -> usize {
+        let mask = match self.to_bitset(itemset) {
+            Ok(mask) => mask,
+            Err(_) => return 0,
+        };
+        self.bitsets()
+            .filter(|bitset| contains_all(bitset, &mask))
+            .count()
+    }
+
+    /// Convert the given items into a bitset.
+    pub(crate) fn to_bitset(&self, items: &[u32]) -> Result<Vec<u64>, Error> {
+        let mut bitset = vec![0; self.words_per_transaction];
+        for &item in items.iter() {
+            let index = item as usize;
+            if index >= self.universe_size {
+                return Err(Error::ItemOutOfUniverse {
+                    item,
+                    universe_size: self.universe_size,
+                });
+            }
+            bitset[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+        }
+        Ok(bitset)
+    }
+
+    /// Return an iterator over the bitsets of all transactions.
+    pub(crate) fn bitsets(&self) -> impl Iterator<Item = &[u64]> {
+        (0..self.transactions_num).map(move |index| {
+            let start = index * self.words_per_transaction;
+            &self.words[start..start + self.words_per_transaction]
+        })
+    }
+
+    /// Return an iterator over the items set in the given `bitset` in ascending order.
+    fn items_of(bitset: &[u64]) -> impl Iterator<Item = u32> + '_ {
+        bitset.iter().enumerate().flat_map(|(index, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some((index * WORD_BITS + bit) as u32)
+            })
+        })
+    }
+}
+
+impl TransactionSource<u32> for BitsetTransactions {
+    fn transactions(&self) -> Box<dyn Iterator<Item = Cow<'_, [u32]>> + '_> {
+        Box::new(
+            self.bitsets()
+                .map(|bitset| Cow::Owned(BitsetTransactions::items_of(bitset).collect())),
+        )
+    }
+
+    fn transactions_num(&self) -> usize {
+        self.transactions_num
+    }
+
+    /// Count the items by their bits, no hashing is needed since an item is set at most once.
+    fn item_supports(&self) -> HashMap<u32, usize> {
+        let mut counts = vec![0; self.universe_size];
+        for bitset in self.bitsets() {
+            for item in BitsetTransactions::items_of(bitset) {
+                counts[item as usize] += 1;
+            }
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .filter(|(_, count)| *count > 0)
+            .map(|(item, count)| (item as u32, count))
+            .collect()
+    }
+}
+
+/// Check whether the `bitset` contains all bits of the `mask` with the same length.
+pub(crate) fn contains_all(bitset: &[u64], mask: &[u64]) -> bool {
+    bitset
+        .iter()
+        .zip(mask.iter())
+        .all(|(word, mask_word)| word & mask_word == *mask_word)
+}
+
+/// Count the support of every item in the `transactions`, an item is counted at most once in
+/// a transaction.
+pub(crate) fn count_item_supports<'a, T: ItemType + 'a>(
+    transactions: impl Iterator<Item = Cow<'a, [T]>>,
+) -> HashMap<T, usize> {
+    count_weighted_item_supports(transactions.map(|transaction| (transaction, 1)))
+}
+
+/// Count the support of every item in the `transactions` occurring the given numbers of times,
+/// an item is counted at most once in a transaction.
+pub(crate) fn count_weighted_item_supports<'a, T: ItemType + 'a>(
+    transactions: impl Iterator<Item = (Cow<'a, [T]>, usize)>,
+) -> HashMap<T, usize> {
+    let mut items = HashMap::new();
+    for (transaction, transaction_count) in transactions {
+        let mut item_set: HashSet<T> = HashSet::new();
+        for &item in transaction.iter() {
+            // Check whether we have inserted the same item in a transaction before,
+            // make sure we won't calculate the wrong support.
+            match item_set.contains(&item) {
+                true => continue,
+                false => {
+                    item_set.insert(item);
+                    let count = items.entry(item).or_insert(0);
+                    *count += transaction_count;
+                }
+            };
+        }
+    }
+    items
+}
+
+/// Parse a line of whitespace-separated item IDs into a transaction.
+fn parse_transaction(line: &str) -> Result<Vec<u32>, std::num::ParseIntError> {
+    line.split_whitespace().map(|item| item.parse()).collect()
+}
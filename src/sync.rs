@@ -0,0 +1,98 @@
+//! `sync` picks the shared pointer and the interior mutability types of the tree nodes. They are
+//! the single-threaded `Rc`, `Cell` and `RefCell` by default, and the `threadsafe` feature swaps
+//! them for `Arc`, an atomic `Cell` and a `RefCell` guarded by a `RwLock`, which makes the trees
+//! `Send + Sync` at the cost of some single-threaded speed.
+
+#[cfg(not(feature = "threadsafe"))]
+pub(crate) use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+
+#[cfg(feature = "threadsafe")]
+pub(crate) use self::threadsafe::{Cell, RefCell};
+#[cfg(feature = "threadsafe")]
+pub(crate) use std::sync::{Arc as Rc, Weak};
+
+#[cfg(feature = "threadsafe")]
+mod threadsafe {
+    use std::{
+        fmt::{self, Debug},
+        mem,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            RwLock, RwLockReadGuard, RwLockWriteGuard,
+        },
+    };
+
+    /// `Cell` is the atomic counterpart of `std::cell::Cell<usize>`, only the node counts need it.
+    #[derive(Default)]
+    pub(crate) struct Cell<T> {
+        value: AtomicUsize,
+        _marker: std::marker::PhantomData<T>,
+    }
+
+    impl Cell<usize> {
+        pub(crate) fn new(value: usize) -> Self {
+            Cell {
+                value: AtomicUsize::new(value),
+                _marker: Default::default(),
+            }
+        }
+
+        pub(crate) fn get(&self) -> usize {
+            self.value.load(Ordering::Relaxed)
+        }
+
+        pub(crate) fn set(&self, value: usize) {
+            self.value.store(value, Ordering::Relaxed);
+        }
+    }
+
+    impl Debug for Cell<usize> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Cell").field("value", &self.get()).finish()
+        }
+    }
+
+    /// `RefCell` mirrors the part of the `std::cell::RefCell` API the tree uses with a `RwLock`.
+    /// A poisoned lock is still accessed, since a panic never leaves a node half updated.
+    #[derive(Default)]
+    pub(crate) struct RefCell<T> {
+        value: RwLock<T>,
+    }
+
+    impl<T> RefCell<T> {
+        pub(crate) fn new(value: T) -> Self {
+            RefCell {
+                value: RwLock::new(value),
+            }
+        }
+
+        pub(crate) fn borrow(&self) -> RwLockReadGuard<'_, T> {
+            self.value.read().unwrap_or_else(|err| err.into_inner())
+        }
+
+        pub(crate) fn borrow_mut(&self) -> RwLockWriteGuard<'_, T> {
+            self.value.write().unwrap_or_else(|err| err.into_inner())
+        }
+
+        pub(crate) fn replace(&self, value: T) -> T {
+            mem::replace(&mut *self.borrow_mut(), value)
+        }
+
+        pub(crate) fn into_inner(self) -> T {
+            self.value
+                .into_inner()
+                .unwrap_or_else(|err| err.into_inner())
+        }
+    }
+
+    impl<T: Debug> Debug for RefCell<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("RefCell")
+                .field("value", &*self.borrow())
+                .finish()
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! `testing` provides the helpers to generate synthetic transactions for the benchmarks and tests.
+//! It's only available with the `testing` feature enabled.
+
+/// `SplitMix64` is a tiny deterministic pseudo random number generator,
+/// so the same seed always generates the same transactions.
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> SplitMix64 {
+        SplitMix64(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a float in `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Generate an integer in `[0, bound)`, `bound` should be positive.
+    pub(crate) fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_f64() * bound as f64) as usize
+    }
+}
+
+/// Generate `num` synthetic transactions over the items `0..items`, each of them holds about
+/// `avg_len` distinct items. The lower item IDs are picked more often, so the items have skewed
+/// supports like the real market-basket data. The same `seed` generates the same transactions.
+pub fn generate_synthetic_transactions(
+    num: usize,
+    items: usize,
+    avg_len: usize,
+    seed: u64,
+) -> Vec<Vec<u32>> {
+    let mut rng = SplitMix64::new(seed);
+    let mut transactions = Vec::with_capacity(num);
+    if items == 0 {
+        transactions.resize(num, vec![]);
+        return transactions;
+    }
+    for _ in 0..num {
+        // The length is uniformly distributed in [1, 2 * avg_len - 1] with `avg_len` as the mean.
+        let len = (1 + rng.next_below(2 * avg_len.max(1) - 1)).min(items);
+        let mut transaction = Vec::with_capacity(len);
+        while transaction.len() < len {
+            let r = rng.next_f64();
+            let item = (r * r * items as f64) as u32;
+            if !transaction.contains(&item) {
+                transaction.push(item);
+            }
+        }
+        transactions.push(transaction);
+    }
+    transactions
+}
+
+/// The number of items in the itemset planted by [`random_transactions()`].
+const PLANTED_ITEMSET_LEN: usize = 3;
+
+/// Generate `num` reproducible synthetic transactions like [`generate_synthetic_transactions()`],
+/// with the itemset returned by [`planted_itemset()`] planted into every other transaction.
+///
+/// The planted itemset works as the ground truth, it and all its subsets must be frequent
+/// with a minimum support up to `(num + 1) / 2`.
+pub fn random_transactions(
+    num: usize,
+    num_items: usize,
+    avg_len: usize,
+    seed: u64,
+) -> Vec<Vec<u32>> {
+    let mut transactions = generate_synthetic_transactions(num, num_items, avg_len, seed);
+    let planted_itemset = planted_itemset(num_items, seed);
+    for transaction in transactions.iter_mut().step_by(2) {
+        for &item in planted_itemset.iter() {
+            if !transaction.contains(&item) {
+                transaction.push(item);
+            }
+        }
+    }
+    transactions
+}
+
+/// Return the itemset planted by [`random_transactions()`] with the same `num_items` and `seed`,
+/// its items are sorted.
+pub fn planted_itemset(num_items: usize, seed: u64) -> Vec<u32> {
+    let mut rng = SplitMix64::new(seed ^ 0x5EED_5EED_5EED_5EED);
+    let mut itemset = Vec::with_capacity(PLANTED_ITEMSET_LEN);
+    while itemset.len() < PLANTED_ITEMSET_LEN.min(num_items) {
+        let item = rng.next_below(num_items) as u32;
+        if !itemset.contains(&item) {
+            itemset.push(item);
+        }
+    }
+    itemset.sort_unstable();
+    itemset
+}
@@ -1,13 +1,12 @@
 //! `Tree` implements the tree data struct in FP-Growth algorithm.
 
 use std::{
-    cell::{Cell, RefCell},
     collections::HashMap,
-    fmt::Debug,
-    rc::{Rc, Weak},
-    usize,
+    fmt::{self, Debug},
+    iter, mem,
 };
 
+use crate::sync::{Cell, Rc, RefCell, Weak};
 use crate::ItemType;
 
 type RcNode<T> = Rc<Node<T>>;
@@ -58,15 +57,15 @@ impl<T: ItemType> Node<T> {
         }
     }
 
-    pub fn remove_child(self: &Rc<Self>, child_node: RcNode<T>) {
+    /// Remove the given child Node from the children of this node.
+    /// The removed node is returned, or `None` if it's not a child of this node.
+    ///
+    /// Only the parent link is changed, the removed nodes are still in the neighbor chains of
+    /// the tree, use [`Tree::remove_node()`] to remove a node from a tree instead.
+    pub fn remove_child(self: &Rc<Self>, child_node: RcNode<T>) -> Option<RcNode<T>> {
         let mut children = self.children.borrow_mut();
-        // for (index, node) in children.clone().into_iter().enumerate() {
-        //     if node == child_node {
-        //         children.remove(index);
-        //     }
-        // }
-        let index = children.iter().position(|x| *x == child_node).unwrap();
-        children.remove(index);
+        let index = children.iter().position(|x| *x == child_node)?;
+        Some(children.remove(index))
     }
 
     /// Check whether this node contains a child node for the given item.
@@ -90,16 +89,29 @@ impl<T: ItemType> Node<T> {
 
     /// Print out the node.
     pub fn print(&self, depth: usize) {
+        let mut output = String::new();
+        // Writing into a `String` never fails.
+        let _ = self.write_subtree(&mut output, depth);
+        print!("{}", output);
+    }
+
+    /// Write this node and its descendants line by line, every line is indented by its depth.
+    fn write_subtree<W: fmt::Write>(&self, output: &mut W, depth: usize) -> fmt::Result {
         let padding = " ".repeat(depth);
-        let node_info;
         match self.is_root() {
-            true => node_info = "<(root)>".to_string(),
-            false => node_info = format!("<{:?} {} (node)>", self.item, self.count.get()),
+            true => writeln!(output, "{}<(root)>", padding)?,
+            false => writeln!(
+                output,
+                "{}<{:?} {} (node)>",
+                padding,
+                self.item,
+                self.count.get()
+            )?,
         }
-        println!("{}{}", padding, node_info);
         for child in self.children.borrow().iter() {
-            child.print(depth + 1);
+            child.write_subtree(output, depth + 1)?;
         }
+        Ok(())
     }
 
     pub fn item(&self) -> Option<T> {
@@ -111,24 +123,159 @@ impl<T: ItemType> Node<T> {
         self.count.get()
     }
 
+    /// Return the number of the transactions which end at this node, i.e. its count minus the
+    /// sum of the counts of its children. It's 0 if the children count more than this node,
+    /// which never happens to a node built from transactions.
+    pub fn terminal_count(&self) -> usize {
+        let children_count: usize = self
+            .children
+            .borrow()
+            .iter()
+            .map(|child| child.count())
+            .sum();
+        self.count().saturating_sub(children_count)
+    }
+
+    /// Return the sum of the counts of this node and all its descendants.
+    ///
+    /// The subtree is walked with an explicit stack rather than recursively, so a deep path
+    /// won't overflow the stack.
+    pub fn subtree_support(&self) -> usize {
+        let mut subtree_support = self.count();
+        let mut pending_nodes: Vec<RcNode<T>> = self.children.borrow().clone();
+        while let Some(node) = pending_nodes.pop() {
+            subtree_support += node.count();
+            pending_nodes.extend(node.children.borrow().iter().cloned());
+        }
+        subtree_support
+    }
+
     /// Return this node's neighbor node.
     pub fn neighbor(&self) -> Option<RcNode<T>> {
         self.neighbor.borrow().upgrade()
     }
 
+    /// Return an iterator over the neighbor chain of this node, which yields the neighbor,
+    /// the neighbor's neighbor and so on. This node itself is not included.
+    pub fn neighbors(&self) -> impl Iterator<Item = RcNode<T>> {
+        Neighbors {
+            next_node: self.neighbor(),
+        }
+    }
+
+    /// Return the children of this node in the order they were added.
+    pub fn children(&self) -> Vec<RcNode<T>> {
+        self.children.borrow().clone()
+    }
+
     /// Return this node's parent node.
     pub fn parent(&self) -> Option<RcNode<T>> {
         self.parent.borrow().upgrade()
     }
 
+    /// Check whether this node is a proper ancestor of the `other` node, a node is not an
+    /// ancestor of itself. The nodes are compared by their identities rather than their items.
+    pub fn is_ancestor_of(self: &Rc<Self>, other: &RcNode<T>) -> bool {
+        let mut cur_node = other.parent();
+        while let Some(node) = cur_node {
+            if Rc::ptr_eq(self, &node) {
+                return true;
+            }
+            cur_node = node.parent();
+        }
+        false
+    }
+
     /// Check whether this node is a root node.
     pub fn is_root(&self) -> bool {
-        self.item == None && self.count.get() == 0
+        self.item.is_none() && self.count.get() == 0
     }
 
     /// Check whether this node is a leaf node.
     pub fn is_leaf(&self) -> bool {
-        self.children.borrow().len() == 0
+        self.children.borrow().is_empty()
+    }
+}
+
+/// `Neighbors<T>` iterates the neighbor chain starting from a node.
+struct Neighbors<T> {
+    next_node: Option<RcNode<T>>,
+}
+
+impl<T: ItemType> Iterator for Neighbors<T> {
+    type Item = RcNode<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next_node.take()?;
+        self.next_node = node.neighbor();
+        Some(node)
+    }
+}
+
+/// `HeaderRow<T>` represents a row of the header table of a tree, i.e. an item with its total
+/// support and the number of nodes holding it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeaderRow<T> {
+    item: T,
+    support: usize,
+    node_count: usize,
+}
+
+impl<T: ItemType> HeaderRow<T> {
+    pub fn item(&self) -> T {
+        self.item
+    }
+
+    /// Return the sum of the counts of all nodes holding the item.
+    pub fn support(&self) -> usize {
+        self.support
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+}
+
+/// `NodePool<T>` keeps the nodes of the recycled trees, so that the trees generated later could
+/// reuse them rather than allocating new ones, see [`Tree::recycle()`].
+pub struct NodePool<T> {
+    nodes: Vec<RcNode<T>>,
+}
+
+impl<T: ItemType> Default for NodePool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ItemType> NodePool<T> {
+    /// Create an empty pool.
+    pub fn new() -> NodePool<T> {
+        NodePool { nodes: vec![] }
+    }
+
+    /// Return the number of the free nodes in this pool.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Take a free node out of this pool and set its item and count,
+    /// a new node is created if the pool is empty.
+    fn take(&mut self, item: Option<T>, count: usize) -> RcNode<T> {
+        match self.nodes.pop() {
+            Some(mut node) => {
+                // Only the nodes without any other reference are put into the pool.
+                let free_node = Rc::get_mut(&mut node).unwrap();
+                free_node.item = item;
+                free_node.count.set(count);
+                node
+            }
+            None => Node::new_rc(item, count),
+        }
     }
 }
 
@@ -139,6 +286,32 @@ pub struct Tree<T> {
     root_node: RefCell<RcNode<T>>,
     // routes is a HashMap who maintains a mapping which satisfies item -> (Head node, tail node).
     routes: HashMap<T, Route<T>>,
+    // node_count is the number of the non-root nodes in this tree.
+    node_count: usize,
+    // name labels this tree in the debugging output if it's set.
+    name: Option<String>,
+}
+
+impl<T> Drop for Tree<T> {
+    /// Drop the nodes iteratively, since dropping a deep path recursively could overflow the
+    /// stack, e.g. the path of a transaction with a huge number of items. The nodes still
+    /// referenced outside the tree are left as they are.
+    fn drop(&mut self) {
+        // The routes hold the references to the nodes as well.
+        self.routes.clear();
+        let mut nodes = vec![self.root_node.replace(Rc::new(Node {
+            item: None,
+            count: Cell::new(0),
+            children: RefCell::new(vec![]),
+            parent: Default::default(),
+            neighbor: Default::default(),
+        }))];
+        while let Some(node) = nodes.pop() {
+            if let Ok(node) = Rc::try_unwrap(node) {
+                nodes.extend(node.children.into_inner());
+            }
+        }
+    }
 }
 
 impl<T: ItemType> Default for Tree<T> {
@@ -153,71 +326,388 @@ impl<T: ItemType> Tree<T> {
         Tree {
             root_node: RefCell::new(Node::new_rc(None, 0)),
             routes: HashMap::new(),
+            node_count: 0,
+            name: None,
         }
     }
 
+    /// Create a new FP-Growth tree which is expected to hold `expected_items` distinct items,
+    /// the route table and the children of the root node are reserved in advance.
+    pub fn with_expected_items(expected_items: usize) -> Tree<T> {
+        let root_node = Node::new_rc(None, 0);
+        root_node.children.borrow_mut().reserve(expected_items);
+        Tree {
+            root_node: RefCell::new(root_node),
+            routes: HashMap::with_capacity(expected_items),
+            node_count: 0,
+            name: None,
+        }
+    }
+
+    /// Set the `name` of this tree, which labels it in the debugging output of
+    /// [`Tree::print()`] and its `Display`, e.g. to tell the partial trees apart.
+    pub fn with_name(mut self, name: String) -> Tree<T> {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     /// Generate a partial tree with the given paths.
     /// This function will be called during the algorithm.
+    ///
+    /// Every path is expected to be a prefix path generated by [`Tree::generate_prefix_path()`],
+    /// the count of its last node is added to every node of the path in the partial tree.
+    /// The empty paths and the root nodes in the paths are ignored.
     pub fn generate_partial_tree(paths: &[Vec<RcNode<T>>]) -> Tree<T> {
+        Tree::generate_partial_tree_in(paths, &mut NodePool::new())
+    }
+
+    /// Generate a partial tree with the given paths like [`Tree::generate_partial_tree()`],
+    /// but take the nodes from the `node_pool` first.
+    pub fn generate_partial_tree_in(
+        paths: &[Vec<RcNode<T>>],
+        node_pool: &mut NodePool<T>,
+    ) -> Tree<T> {
         let mut partial_tree = Tree::new();
-        let mut leaf_item = None;
         for path in paths.iter() {
             // Get leaf_count from the leaf node.
-            leaf_item = Some(path.last().unwrap().item.unwrap());
-            let mut cur_node = Rc::clone(&partial_tree.root_node.borrow());
-            for path_node in path.iter() {
-                match cur_node.search(path_node.item.unwrap()) {
-                    Some(child_node) => {
-                        cur_node = child_node;
-                    }
-                    None => {
-                        let next_node = Node::new_rc(path_node.item, {
-                            let mut count = 0;
-                            if path_node.item == leaf_item {
-                                count = path_node.count.get();
-                            }
-                            count
-                        });
-                        cur_node.add_child(Rc::clone(&next_node));
-                        partial_tree.update_route(Rc::clone(&next_node));
-                        cur_node = next_node;
-                    }
+            let leaf_count = match path.last() {
+                Some(leaf_node) => leaf_node.count(),
+                None => continue,
+            };
+            let items = path.iter().filter_map(|path_node| path_node.item);
+            partial_tree.add_path_in(items, leaf_count, node_pool);
+        }
+
+        partial_tree
+    }
+
+    /// Generate the prefix paths that end with the given item like
+    /// [`Tree::generate_prefix_path()`], but every path is represented by its items and the
+    /// count of its last node rather than the references to the nodes, which is all a partial
+    /// tree needs.
+    pub fn generate_prefix_path_items(&self, item: T) -> Vec<(Vec<T>, usize)> {
+        let mut paths = vec![];
+        for end_node in self.get_all_nodes(item).into_iter() {
+            let end_count = end_node.count();
+            let mut path = vec![];
+            let mut cur_node = Some(end_node);
+            while let Some(node) = cur_node {
+                match node.item {
+                    Some(item) => path.push(item),
+                    None => break,
                 }
+                cur_node = node.parent();
             }
+            path.reverse();
+            paths.push((path, end_count));
         }
+        paths
+    }
+
+    /// Generate a partial tree with the given paths of items and counts generated by
+    /// [`Tree::generate_prefix_path_items()`], the count of every path is added to all its
+    /// nodes in the partial tree. The empty paths are ignored.
+    pub fn generate_partial_tree_from_items(paths: &[(Vec<T>, usize)]) -> Tree<T> {
+        Tree::generate_partial_tree_from_items_in(paths, &mut NodePool::new())
+    }
 
-        // Calculate the counts of the non-leaf nodes.
-        for path in partial_tree.generate_prefix_path(leaf_item.unwrap()).iter() {
-            let leaf_count = path.last().unwrap().count.get();
-            for path_node in path[..path.len() - 1].iter() {
-                path_node.increment(leaf_count);
+    /// Generate a partial tree with the given paths of items and counts like
+    /// [`Tree::generate_partial_tree_from_items()`], but take the nodes from the `node_pool` first.
+    pub fn generate_partial_tree_from_items_in(
+        paths: &[(Vec<T>, usize)],
+        node_pool: &mut NodePool<T>,
+    ) -> Tree<T> {
+        let mut partial_tree = Tree::new();
+        for (path, count) in paths.iter() {
+            if path.is_empty() {
+                continue;
             }
+            partial_tree.add_path_in(path.iter().copied(), *count, node_pool);
         }
 
         partial_tree
     }
 
+    /// Add the `items` as a path from the root and `count` to every node of the path, the new
+    /// nodes are taken from the `node_pool` first.
+    fn add_path_in(
+        &mut self,
+        items: impl Iterator<Item = T>,
+        count: usize,
+        node_pool: &mut NodePool<T>,
+    ) {
+        let mut cur_node = Rc::clone(&self.root_node.borrow());
+        for item in items {
+            match cur_node.search(item) {
+                Some(child_node) => {
+                    child_node.increment(count);
+                    cur_node = child_node;
+                }
+                None => {
+                    let next_node = node_pool.take(Some(item), count);
+                    cur_node.add_child(Rc::clone(&next_node));
+                    self.update_route(Rc::clone(&next_node));
+                    self.node_count += 1;
+                    cur_node = next_node;
+                }
+            }
+        }
+    }
+
     /// Iterate the transaction and add every item to the FP-Growth tree.
     pub fn add_transaction(&mut self, transaction: Vec<T>) {
+        self.add_transaction_with_count(transaction, 1);
+    }
+
+    /// Add the transaction occurring `count` times to the FP-Growth tree at once, which is the
+    /// same as adding it `count` times.
+    pub fn add_transaction_with_count(&mut self, transaction: Vec<T>, count: usize) {
         let mut cur_node = Rc::clone(&self.root_node.borrow());
         for &item in transaction.iter() {
             match cur_node.search(item) {
                 // There is already a node in this tree for the current
                 // transaction item; reuse it.
                 Some(child_node) => {
-                    child_node.increment(1);
+                    child_node.increment(count);
                     cur_node = child_node;
                 }
                 None => {
-                    let next_node = Node::new_rc(Some(item), 1);
+                    let next_node = Node::new_rc(Some(item), count);
                     cur_node.add_child(Rc::clone(&next_node));
                     self.update_route(Rc::clone(&next_node));
+                    self.node_count += 1;
                     cur_node = next_node;
                 }
             }
         }
     }
 
+    /// Remove a transaction added before with its items in the same order, the count of every
+    /// node on its path is decremented and the nodes whose count becomes 0 are removed.
+    /// Return `false` without changing anything if no transaction ends at the end of such a
+    /// path in this tree, e.g. the path is only a prefix of the transactions added.
+    pub fn remove_transaction(&mut self, transaction: Vec<T>) -> bool {
+        let mut path = Vec::with_capacity(transaction.len());
+        let mut cur_node = Rc::clone(&self.root_node.borrow());
+        for &item in transaction.iter() {
+            match cur_node.search(item) {
+                Some(child_node) => {
+                    path.push(Rc::clone(&child_node));
+                    cur_node = child_node;
+                }
+                None => return false,
+            }
+        }
+        if path.last().is_some_and(|node| node.terminal_count() == 0) {
+            return false;
+        }
+        for node in path.iter() {
+            node.count.set(node.count() - 1);
+        }
+        // A node never counts more than its parent, so the nodes of count 0 are at the end of
+        // the path, and they have no other descendant.
+        if let Some(empty_node) = path.iter().find(|node| node.count() == 0) {
+            self.remove_node(empty_node);
+        }
+        true
+    }
+
+    /// Drop this tree and put its nodes into the `node_pool` to be reused. The nodes still
+    /// referenced outside the tree are left as they are, so are their descendants.
+    pub fn recycle(mut self, node_pool: &mut NodePool<T>) {
+        // The routes hold the references to the nodes as well.
+        self.routes.clear();
+        let mut nodes = mem::take(&mut *self.root_node.borrow().children.borrow_mut());
+        let mut free_nodes = Vec::with_capacity(self.node_count);
+        while let Some(node) = nodes.pop() {
+            if Rc::strong_count(&node) == 1 {
+                *node.parent.borrow_mut() = Weak::new();
+                *node.neighbor.borrow_mut() = Weak::new();
+                nodes.append(&mut node.children.borrow_mut());
+                free_nodes.push(node);
+            }
+        }
+        // A node could be reused only after all the weak references to it are reset.
+        node_pool.nodes.extend(
+            free_nodes
+                .into_iter()
+                .filter(|node| Rc::weak_count(node) == 0),
+        );
+    }
+
+    /// Merge the `other` tree into this one, the counts of the shared paths are summed and the
+    /// other nodes are added with their routes. If both trees insert the items of transactions
+    /// in the same order, the result is the same as the tree built from all their transactions.
+    pub fn merge(&mut self, other: Tree<T>) {
+        let mut node_pairs = vec![(
+            Rc::clone(&self.root_node.borrow()),
+            Rc::clone(&other.root_node.borrow()),
+        )];
+        while let Some((node, other_node)) = node_pairs.pop() {
+            for other_child in other_node.children.borrow().iter() {
+                // The root node is the only node without an item.
+                let item = match other_child.item {
+                    Some(item) => item,
+                    None => continue,
+                };
+                let child = match node.search(item) {
+                    Some(child) => {
+                        child.increment(other_child.count());
+                        child
+                    }
+                    None => {
+                        let child = Node::new_rc(Some(item), other_child.count());
+                        node.add_child(Rc::clone(&child));
+                        self.update_route(Rc::clone(&child));
+                        self.node_count += 1;
+                        child
+                    }
+                };
+                node_pairs.push((child, Rc::clone(other_child)));
+            }
+        }
+    }
+
+    /// Return the root node of this tree, from which the whole tree could be walked by
+    /// [`Node::children()`].
+    pub fn root(&self) -> RcNode<T> {
+        Rc::clone(&self.root_node.borrow())
+    }
+
+    /// Return the number of nodes in this tree, the root node is not included.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Remove the given `node` with all its descendants from this tree, every removed node is
+    /// unlinked from its neighbor chain as well, so the route table stays consistent. The count
+    /// of the node is subtracted from all its ancestors, as if the transactions through it were
+    /// never added, an ancestor left with count 0 is kept in the tree though.
+    /// Return `false` if the node is not in this tree, e.g. it's the root node or it's removed.
+    pub fn remove_node(&mut self, node: &RcNode<T>) -> bool {
+        let parent_node = match node.parent() {
+            Some(parent_node) => parent_node,
+            None => return false,
+        };
+        let in_tree = match node.item {
+            Some(item) => self
+                .get_all_nodes(item)
+                .iter()
+                .any(|route_node| Rc::ptr_eq(route_node, node)),
+            None => false,
+        };
+        if !in_tree {
+            return false;
+        }
+        {
+            let mut children = parent_node.children.borrow_mut();
+            children.retain(|child_node| !Rc::ptr_eq(child_node, node));
+        }
+        *node.parent.borrow_mut() = Weak::new();
+        let mut ancestor_node = Some(parent_node);
+        while let Some(cur_node) = ancestor_node {
+            if cur_node.is_root() {
+                break;
+            }
+            cur_node
+                .count
+                .set(cur_node.count().saturating_sub(node.count()));
+            ancestor_node = cur_node.parent();
+        }
+
+        let mut removed_nodes = vec![Rc::clone(node)];
+        while let Some(removed_node) = removed_nodes.pop() {
+            self.unlink_route(&removed_node);
+            self.node_count -= 1;
+            removed_nodes.extend(removed_node.children.borrow().iter().cloned());
+        }
+        true
+    }
+
+    /// Multiply the count of every node by `factor` and round it down, the nodes whose count
+    /// becomes 0 are removed with their descendants, and the route table is rebuilt for the
+    /// rest. Calling it periodically with a `factor` in (0, 1) forgets the old transactions
+    /// exponentially, so the recent ones dominate the patterns mined from a stream.
+    ///
+    /// Since a node never counts more than its parent, neither does it after the decay.
+    /// It panics if `factor` is not in `0.0..=1.0`, e.g. it's NaN, since a larger factor would
+    /// inflate the counts rather than forget the transactions.
+    pub fn decay(&mut self, factor: f64) {
+        assert!(
+            (0.0..=1.0).contains(&factor),
+            "the decay factor {} is not in 0.0..=1.0",
+            factor
+        );
+        self.routes.clear();
+        self.node_count = 0;
+        let mut pruned_nodes = vec![];
+        let mut pending_nodes = vec![Rc::clone(&self.root_node.borrow())];
+        while let Some(node) = pending_nodes.pop() {
+            let mut children = node.children.borrow_mut();
+            children.retain(|child_node| {
+                let count = (child_node.count() as f64 * factor) as usize;
+                child_node.count.set(count);
+                if count == 0 {
+                    pruned_nodes.push(Rc::clone(child_node));
+                }
+                count > 0
+            });
+            for child_node in children.iter() {
+                *child_node.neighbor.borrow_mut() = Weak::new();
+                self.update_route(Rc::clone(child_node));
+                self.node_count += 1;
+                pending_nodes.push(Rc::clone(child_node));
+            }
+        }
+        // Drop the pruned nodes iteratively like dropping a tree, since a path could be deep.
+        while let Some(node) = pruned_nodes.pop() {
+            *node.parent.borrow_mut() = Weak::new();
+            if let Ok(node) = Rc::try_unwrap(node) {
+                pruned_nodes.extend(node.children.into_inner());
+            }
+        }
+    }
+
+    /// Unlink the given `node` from the neighbor chain of its item.
+    fn unlink_route(&mut self, node: &RcNode<T>) {
+        let item = match node.item {
+            Some(item) => item,
+            None => return,
+        };
+        let next_node = node.neighbor();
+        *node.neighbor.borrow_mut() = Weak::new();
+        let (head, tail) = match self.routes.get(&item) {
+            Some(route) => route,
+            None => return,
+        };
+        if Rc::ptr_eq(&head.borrow(), node) {
+            match next_node {
+                Some(next_node) => *head.borrow_mut() = next_node,
+                None => {
+                    self.routes.remove(&item);
+                }
+            }
+            return;
+        }
+        let head_node = Rc::clone(&head.borrow());
+        let prev_node = iter::once(head_node)
+            .chain(head.borrow().neighbors())
+            .find(|route_node| route_node.neighbor().is_some_and(|n| Rc::ptr_eq(&n, node)));
+        if let Some(prev_node) = prev_node {
+            *prev_node.neighbor.borrow_mut() = match &next_node {
+                Some(next_node) => Rc::downgrade(next_node),
+                None => Weak::new(),
+            };
+            if Rc::ptr_eq(&tail.borrow(), node) {
+                *tail.borrow_mut() = prev_node;
+            }
+        }
+    }
+
     /// Update the route table that records the item and its node list.
     pub fn update_route(&mut self, node: RcNode<T>) {
         if let Some(item) = node.item {
@@ -235,12 +725,12 @@ impl<T: ItemType> Tree<T> {
     }
 
     /// Generate the prefix paths that end with the given item.
+    /// No path is generated if the item is not in this tree.
     pub fn generate_prefix_path(&self, item: T) -> Vec<Vec<RcNode<T>>> {
-        let mut cur_end_node = Rc::clone(&self.routes.get(&item).unwrap().0.borrow());
         let mut paths = vec![];
-        loop {
-            let mut cur_node = Rc::clone(&cur_end_node);
-            let mut path = vec![Rc::clone(&cur_node)];
+        for end_node in self.get_all_nodes(item).into_iter() {
+            let mut cur_node = Rc::clone(&end_node);
+            let mut path = vec![end_node];
             while let Some(parent_node) = cur_node.parent() {
                 if parent_node.is_root() {
                     break;
@@ -250,28 +740,134 @@ impl<T: ItemType> Tree<T> {
             }
             path.reverse();
             paths.push(path);
-            match cur_end_node.neighbor() {
-                Some(neighbor_node) => cur_end_node = neighbor_node,
-                None => break,
-            }
         }
         paths
     }
 
+    /// Return the number of transactions in this tree which contain all the given `items`, even
+    /// if the itemset is not frequent. The empty itemset is contained by every transaction.
+    ///
+    /// Every transaction containing the itemset is counted at the first node of its path by
+    /// which all the items are held, i.e. a node of an item in the itemset whose ancestors hold
+    /// all the other items but not its own, so a transaction repeating an item is still counted
+    /// once. Those nodes are found by walking up from every node of the items in the header
+    /// table.
+    pub fn itemset_support(&self, items: &[T]) -> usize {
+        TreeQueries::itemset_support(self, items)
+    }
+
+    /// Return every item in this tree with its support, in the order of the route table.
+    pub(crate) fn item_supports(&self) -> Vec<(T, usize)> {
+        self.routes
+            .iter()
+            .map(|(item, (head_node, _))| {
+                let head_node = Rc::clone(&head_node.borrow());
+                let neighbors = head_node.neighbors();
+                let support = iter::once(head_node)
+                    .chain(neighbors)
+                    .map(|node| node.count())
+                    .sum();
+                (*item, support)
+            })
+            .collect()
+    }
+
     /// Get all nodes that holds the given item.
     pub fn get_all_nodes(&self, item: T) -> Vec<RcNode<T>> {
         match self.routes.get(&item) {
             None => vec![],
             Some((head_node, _)) => {
-                let mut nodes = vec![Rc::clone(&head_node.borrow())];
-                let mut cur_node = Rc::clone(&head_node.borrow());
-                while let Some(neighbor_node) = cur_node.neighbor() {
-                    nodes.push(Rc::clone(&neighbor_node));
-                    cur_node = neighbor_node;
-                }
-                nodes
+                let head_node = Rc::clone(&head_node.borrow());
+                let neighbors = head_node.neighbors();
+                iter::once(head_node).chain(neighbors).collect()
+            }
+        }
+    }
+
+    /// Reconstruct the distinct transactions inserted into this tree with their multiplicities,
+    /// the items of every transaction are in the same order as they were inserted. Since a
+    /// transaction ends at a node if the node's count is larger than the sum of its children's,
+    /// the multiplicities add up to the number of the non-empty inserted transactions.
+    pub fn reconstruct_transactions(&self) -> Vec<(Vec<T>, usize)> {
+        let mut transactions = vec![];
+        let root_node = Rc::clone(&self.root_node.borrow());
+        let mut pending_nodes: Vec<(RcNode<T>, Vec<T>)> = root_node
+            .children
+            .borrow()
+            .iter()
+            .rev()
+            .map(|node| (Rc::clone(node), vec![]))
+            .collect();
+        while let Some((node, mut path)) = pending_nodes.pop() {
+            if let Some(item) = node.item {
+                path.push(item);
+            }
+            let terminal_count = node.terminal_count();
+            if terminal_count > 0 {
+                transactions.push((path.clone(), terminal_count));
+            }
+            for child in node.children.borrow().iter().rev() {
+                pending_nodes.push((Rc::clone(child), path.clone()));
             }
         }
+        transactions
+    }
+
+    /// Return the depth of every leaf node, i.e. the number of items on its path from the root.
+    pub(crate) fn leaf_depths(&self) -> Vec<usize> {
+        let mut leaf_depths = vec![];
+        let mut pending_nodes = vec![(Rc::clone(&self.root_node.borrow()), 0)];
+        while let Some((node, depth)) = pending_nodes.pop() {
+            let children = node.children.borrow();
+            if children.is_empty() && depth > 0 {
+                leaf_depths.push(depth);
+            }
+            for child in children.iter() {
+                pending_nodes.push((Rc::clone(child), depth + 1));
+            }
+        }
+        leaf_depths
+    }
+
+    /// Convert this tree into a [`FrozenTree`], an immutable form which could be shared and
+    /// queried across threads.
+    pub fn freeze(self) -> FrozenTree<T> {
+        let root_node = Rc::clone(&self.root_node.borrow());
+        let mut nodes = vec![];
+        // indexes maps the address of every node to its index in the frozen tree.
+        let mut indexes: HashMap<*const Node<T>, usize> = HashMap::new();
+        let mut pending_nodes = vec![(root_node, None)];
+        while let Some((node, parent)) = pending_nodes.pop() {
+            let index = nodes.len();
+            indexes.insert(Rc::as_ptr(&node), index);
+            nodes.push(FrozenNode {
+                item: node.item,
+                count: node.count(),
+                parent,
+            });
+            for child_node in node.children.borrow().iter() {
+                pending_nodes.push((Rc::clone(child_node), Some(index)));
+            }
+        }
+        let routes = self
+            .get_all_items_nodes()
+            .into_iter()
+            .map(|(item, item_nodes)| {
+                let item_indexes = item_nodes
+                    .iter()
+                    .map(|node| indexes[&Rc::as_ptr(node)])
+                    .collect();
+                (item, item_indexes)
+            })
+            .collect();
+        FrozenTree { nodes, routes }
+    }
+
+    /// Return the header table of this tree, every item comes with its support and the number
+    /// of nodes holding it. The rows are sorted by the support in descending order and then by
+    /// the item.
+    pub fn header_summary(&self) -> Vec<HeaderRow<T>> {
+        TreeQueries::header_summary(self)
     }
 
     /// Get all nodes with the given item.
@@ -348,17 +944,216 @@ impl<T: ItemType> Tree<T> {
     }
 
     #[allow(dead_code)]
-    /// Print out the tree.
+    /// Print out the tree, followed by the nodes of every item in the route table.
     pub fn print(&self) {
-        println!("Tree:");
-        self.root_node.borrow().print(1);
+        print!("{}", self);
         println!("Routes:");
         for (item, _) in self.routes.iter() {
             println!("Item: {:?}", *item);
             for node in self.get_all_nodes(*item).iter() {
-                println!("{:?}", Rc::into_raw(Rc::clone(node)));
+                println!("{:?}", Rc::as_ptr(node));
                 println!("<{:?} {}>", node.item, node.count.get());
             }
         }
     }
 }
+
+impl<T: ItemType> fmt::Display for Tree<T> {
+    /// Write the header labeled with the name of the tree if it's set, and then all the nodes
+    /// from the root node, indented by their depths.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => writeln!(f, "Tree {}:", name)?,
+            None => writeln!(f, "Tree:")?,
+        }
+        self.root_node.borrow().write_subtree(f, 1)
+    }
+}
+
+/// `FrozenNode<T>` is a node of a [`FrozenTree`], its parent is referred by the index.
+#[derive(Clone, Debug)]
+struct FrozenNode<T> {
+    item: Option<T>,
+    count: usize,
+    parent: Option<usize>,
+}
+
+/// `FrozenTree<T>` is the immutable form of a [`Tree`] created by [`Tree::freeze()`]. The nodes
+/// are stored in a single vector and refer to each other by their indexes rather than `Rc`, so
+/// it is `Send` and `Sync` and could be shared across threads with an `Arc` to run read-only
+/// queries in parallel.
+#[derive(Clone, Debug)]
+pub struct FrozenTree<T> {
+    // nodes holds all nodes with the root node first.
+    nodes: Vec<FrozenNode<T>>,
+    // routes maps every item to the indexes of its nodes in the order of the neighbor chain.
+    routes: HashMap<T, Vec<usize>>,
+}
+
+impl<T: ItemType> FrozenTree<T> {
+    /// Return the number of nodes in this tree, the root node is not included.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    /// Return the counts of all nodes holding the given `item`.
+    pub fn item_node_counts(&self, item: T) -> Vec<usize> {
+        self.routes.get(&item).map_or(vec![], |indexes| {
+            indexes
+                .iter()
+                .map(|&index| self.nodes[index].count)
+                .collect()
+        })
+    }
+
+    /// Return the same support as [`Tree::itemset_support()`].
+    pub fn itemset_support(&self, items: &[T]) -> usize {
+        TreeQueries::itemset_support(self, items)
+    }
+
+    /// Return the same header table as [`Tree::header_summary()`].
+    pub fn header_summary(&self) -> Vec<HeaderRow<T>> {
+        TreeQueries::header_summary(self)
+    }
+}
+
+/// `TreeQueries<T>` abstracts the read-only queries answered the same way by [`Tree`] and
+/// [`FrozenTree`], which only differ in how their nodes are referred to.
+trait TreeQueries<T: ItemType> {
+    /// The reference to a node, e.g. an `Rc` or an index.
+    type NodeRef;
+
+    /// Return the nodes holding the given `item` in the order of the neighbor chain.
+    fn item_nodes(&self, item: T) -> Vec<Self::NodeRef>;
+
+    /// Return every item in the tree with its nodes.
+    fn all_item_nodes(&self) -> Vec<(T, Vec<Self::NodeRef>)>;
+
+    fn item_of(&self, node: &Self::NodeRef) -> Option<T>;
+
+    fn count_of(&self, node: &Self::NodeRef) -> usize;
+
+    fn parent_of(&self, node: &Self::NodeRef) -> Option<Self::NodeRef>;
+
+    /// Return the sum of the counts of the children of the root node.
+    fn root_children_count(&self) -> usize;
+
+    fn itemset_support(&self, items: &[T]) -> usize {
+        let mut items = items.to_vec();
+        items.sort();
+        items.dedup();
+        if items.is_empty() {
+            return self.root_children_count();
+        }
+        items
+            .iter()
+            .flat_map(|&item| self.item_nodes(item))
+            .filter(|node| {
+                let node_item = self.item_of(node);
+                let mut held_items = vec![false; items.len()];
+                let mut missing_items = items.len() - 1;
+                let mut cur_node = self.parent_of(node);
+                while let Some(parent_node) = cur_node {
+                    if let Some(item) = self.item_of(&parent_node) {
+                        // The transactions through this node are counted at the ancestor.
+                        if Some(item) == node_item {
+                            return false;
+                        }
+                        if let Ok(index) = items.binary_search(&item) {
+                            if !held_items[index] {
+                                held_items[index] = true;
+                                missing_items -= 1;
+                            }
+                        }
+                    }
+                    cur_node = self.parent_of(&parent_node);
+                }
+                missing_items == 0
+            })
+            .map(|node| self.count_of(&node))
+            .sum()
+    }
+
+    fn header_summary(&self) -> Vec<HeaderRow<T>> {
+        let mut header_rows: Vec<HeaderRow<T>> = self
+            .all_item_nodes()
+            .into_iter()
+            .map(|(item, nodes)| HeaderRow {
+                item,
+                support: nodes.iter().map(|node| self.count_of(node)).sum(),
+                node_count: nodes.len(),
+            })
+            .collect();
+        header_rows.sort_by(|a, b| b.support.cmp(&a.support).then_with(|| a.item.cmp(&b.item)));
+        header_rows
+    }
+}
+
+impl<T: ItemType> TreeQueries<T> for Tree<T> {
+    type NodeRef = RcNode<T>;
+
+    fn item_nodes(&self, item: T) -> Vec<RcNode<T>> {
+        self.get_all_nodes(item)
+    }
+
+    fn all_item_nodes(&self) -> Vec<(T, Vec<RcNode<T>>)> {
+        self.get_all_items_nodes()
+    }
+
+    fn item_of(&self, node: &RcNode<T>) -> Option<T> {
+        node.item
+    }
+
+    fn count_of(&self, node: &RcNode<T>) -> usize {
+        node.count()
+    }
+
+    fn parent_of(&self, node: &RcNode<T>) -> Option<RcNode<T>> {
+        node.parent()
+    }
+
+    fn root_children_count(&self) -> usize {
+        self.root_node
+            .borrow()
+            .children
+            .borrow()
+            .iter()
+            .map(|node| node.count())
+            .sum()
+    }
+}
+
+impl<T: ItemType> TreeQueries<T> for FrozenTree<T> {
+    type NodeRef = usize;
+
+    fn item_nodes(&self, item: T) -> Vec<usize> {
+        self.routes.get(&item).cloned().unwrap_or_default()
+    }
+
+    fn all_item_nodes(&self) -> Vec<(T, Vec<usize>)> {
+        self.routes
+            .iter()
+            .map(|(&item, indexes)| (item, indexes.clone()))
+            .collect()
+    }
+
+    fn item_of(&self, &index: &usize) -> Option<T> {
+        self.nodes[index].item
+    }
+
+    fn count_of(&self, &index: &usize) -> usize {
+        self.nodes[index].count
+    }
+
+    fn parent_of(&self, &index: &usize) -> Option<usize> {
+        self.nodes[index].parent
+    }
+
+    fn root_children_count(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter(|node| node.parent == Some(0))
+            .map(|node| node.count)
+            .sum()
+    }
+}